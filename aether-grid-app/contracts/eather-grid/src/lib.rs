@@ -16,14 +16,25 @@
 //! - `xy_nullifier_hashed` → public output: `pedersen_hash([x, y, nullifier])`.
 //!
 //! ## Nullifier Design
-//! To prevent cross-session replay, the frontend MUST derive the nullifier as:
-//!   `nullifier = keccak256(session_id ‖ player1_address ‖ player2_address)`
-//! This binds each proof cryptographically to a single session.
-//! The resulting `xy_nullifier_hashed` is therefore unique per session.
+//! To prevent cross-session replay, the nullifier is derived as:
+//!   `nullifier = keccak256([DOMAIN_TAG ‖] session_id ‖ player1_address ‖ player2_address ‖ contract_address [‖ deadline_ledger])`
+//! This binds each proof cryptographically to a single session of a single
+//! deployment: two contracts reusing the same session ids and players still
+//! derive different nullifiers.  The resulting `xy_nullifier_hashed` is
+//! therefore unique per session.  A game started with a `deadline_ledger`
+//! also binds it, big-endian.
+//!
+//! The contract derives and stores the nullifier at `start_game`; frontends
+//! obtain it beforehand from `derive_nullifier` (or afterwards from
+//! `get_nullifier`) rather than re-implementing the derivation.  The domain
+//! tag is only prepended when domain separation is enabled; each game records
+//! the derivation version it was started with.
 //!
 //! ## Flow
 //! 1. Admin deploys UltraHonk verifier (VK embedded at compile time).
 //! 2. Admin deploys this contract with (`admin`, `game_hub`, `verifier`).
+//!    Standalone deployments pass no `game_hub`; games then settle purely
+//!    in contract state and no hub is ever called.
 //! 3. Frontend calls `start_game` and supplies `treasure_hash` =
 //!    the Pedersen hash that the treasure's canonical coordinates produce.
 //! 4. Each player calls `submit_zk_proof(session_id, player, proof, public_inputs, energy_used)`.
@@ -31,8 +42,20 @@
 //!    - `verifier.verify_proof` traps on failure; success records `energy_used`.
 //! 5. Caller invokes `resolve_game` → winner determined by energy efficiency:
 //!    - One verified  → that player wins.
-//!    - Both verified → lower `energy_used` wins; tie goes to player1.
-//!    - Neither       → both lose; GameHub notified with `player1_won = false`.
+//!    - Both verified → lower `energy_used` wins; a tie is a draw.
+//!    - Neither       → both lose; GameHub notified with outcome 3 (nobody won).
+//!
+//! ## Bridge Event
+//! Every resolution publishes `BridgeResult` under the topics
+//! `("bridge", "result")` with a single `Bytes` payload of exactly 70 bytes:
+//!
+//! | Offset | Len | Field                                                    |
+//! |--------|-----|----------------------------------------------------------|
+//! | 0      | 4   | `session_id`, u32 little-endian                          |
+//! | 4      | 1   | outcome: 0 = Player1Won, 1 = Player2Won, 2 = BothFoundTreasure, 3 = NeitherFound |
+//! | 5      | 32  | `keccak256(player1 strkey)`, the same bytes used in the nullifier preimage |
+//! | 37     | 32  | `keccak256(player2 strkey)`                              |
+//! | 69     | 1   | `player1_won`: 1 if player 1 is the reported winner, else 0 (including ties) |
 //!
 //! ## Trust Boundaries
 //! - Verifier is stateless and decoupled; VK is baked in at deploy.
@@ -40,9 +63,12 @@
 //! - `energy_used` is caller-supplied and NOT circuit-constrained in this version.
 //!   A future circuit version should include it as a public output.
 
+use core::cmp::Ordering;
+
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype, vec, Address, Bytes,
-    BytesN, Env, IntoVal,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    panic_with_error, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Executable, IntoVal, Map,
+    MuxedAddress, Symbol, TryFromVal, Val, Vec,
 };
 
 // ============================================================================
@@ -52,6 +78,9 @@ use soroban_sdk::{
 /// Interface for the mock-game-hub contract.
 #[contractclient(name = "GameHubClient")]
 pub trait GameHub {
+    /// Returns nothing, or a `u64` lock id that the hub wants back at
+    /// settlement (see `end_game_with_outcome_and_lock`,
+    /// `settle_with_stakes_and_lock` and `refund_game_with_lock`).
     fn start_game(
         env: Env,
         game_id: Address,
@@ -60,8 +89,48 @@ pub trait GameHub {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
-    );
+    ) -> Val;
+    /// Legacy boolean settlement; no longer called, since it cannot report
+    /// a draw or a game nobody won (see `end_game_with_outcome`).
     fn end_game(env: Env, session_id: u32, player1_won: bool);
+    /// Settle a session with its reported result: 0 = player 1 won,
+    /// 1 = player 2 won, 2 = draw, 3 = nobody won.
+    fn end_game_with_outcome(env: Env, session_id: u32, outcome: u32);
+    /// Legacy boolean form of `end_game_with_outcome_and_lock`; no longer
+    /// called.
+    fn end_game_with_lock(env: Env, session_id: u32, player1_won: bool, lock_id: u64);
+    /// Used instead of `end_game_with_outcome` for sessions whose
+    /// `start_game` returned a non-zero lock id; `outcome` uses the same
+    /// codes.
+    fn end_game_with_outcome_and_lock(env: Env, session_id: u32, outcome: u32, lock_id: u64);
+    /// Stake-aware settlement used instead of `end_game_with_outcome` when
+    /// `DataKey::StakeAwareSettlement` is on and the game has a winner.
+    fn settle_with_stakes(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        winner_stake: i128,
+        loser_stake: i128,
+    );
+    /// Used instead of `settle_with_stakes` for sessions whose `start_game`
+    /// returned a non-zero lock id.
+    fn settle_with_stakes_and_lock(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        winner_stake: i128,
+        loser_stake: i128,
+        lock_id: u64,
+    );
+    /// Release both players' locked points without a winner; used by
+    /// `admin_refund`.
+    fn refund_game(env: Env, session_id: u32);
+    /// Used instead of `refund_game` for sessions whose `start_game`
+    /// returned a non-zero lock id.
+    fn refund_game_with_lock(env: Env, session_id: u32, lock_id: u64);
+    /// Forget a session both players abandoned before verifying, unlocking
+    /// their points; used by `cancel_game`.
+    fn cancel_game(env: Env, session_id: u32);
 }
 
 /// Interface for the UltraHonk verifier contract.
@@ -78,6 +147,21 @@ pub trait UltraHonkVerifier {
     fn verify_proof(env: Env, public_inputs: Bytes, proof_bytes: Bytes);
 }
 
+/// Interface for verifiers that report failure by returning `false` instead
+/// of trapping.  Used for games whose `VerifierMode` is `Bool`; the same
+/// parameter order applies.
+#[contractclient(name = "BoolVerifierClient")]
+pub trait BoolVerifier {
+    fn verify_proof(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> bool;
+}
+
+/// Optional verifier extension reporting an upstream pause.  Verifiers
+/// without it are treated as never paused.
+#[contractclient(name = "PausableVerifierClient")]
+pub trait PausableVerifier {
+    fn is_paused(env: Env) -> bool;
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -99,6 +183,136 @@ pub enum Error {
     /// `public_inputs` bytes do not match `game.treasure_hash`.
     /// Prevents cross-session replay attacks.
     PublicInputMismatch = 6,
+    /// The session's submission cap has been reached.
+    SubmissionBudgetExhausted = 7,
+    /// The game exists but has not been resolved yet.  Returned uniformly by
+    /// every view that depends on a resolution.
+    GameNotResolved = 8,
+    /// The session's `time_limit_seconds` has elapsed since it started.
+    TimeLimitExceeded = 9,
+    /// A batch call exceeded its maximum input length.
+    BatchTooLarge = 10,
+    /// Eligibility gating is on and a player is not on the allowlist.
+    NotEligible = 11,
+    /// A muxed address was supplied whose underlying account is not a player
+    /// in this session.
+    MuxedAddressMismatch = 12,
+    /// `start_game_named` was called with a name already bound to a session.
+    NameTaken = 13,
+    /// A proof chunk arrived out of order, or `finalize_proof` was called
+    /// before every chunk was uploaded.
+    IncompleteProof = 14,
+    /// A `Bool`-mode verifier returned `false` for the submitted proof.
+    InvalidProof = 15,
+    /// The verifier address is not a deployed Wasm contract.
+    NotAContract = 16,
+    /// `start_game` reused, or `close_session` deleted, the id of a session
+    /// resolved less than `SessionReuseCooldown` ledgers ago.
+    SessionReuseTooSoon = 17,
+    /// A player committed more points than the session's points cap.
+    PointsAboveCap = 18,
+    /// A points cap was not positive or exceeded `MAX_POINTS_CEILING`.
+    InvalidPointsCap = 19,
+    /// The session has no winnings awaiting a claim.
+    NothingToClaim = 20,
+    /// `sweep_unclaimed` was called before the claim window elapsed.
+    ClaimWindowOpen = 21,
+    /// The game cannot be reclaimed while its winnings await a claim.
+    ClaimPending = 22,
+    /// A game already exists for this session id.
+    SessionExists = 23,
+    /// `RequireDistinctProofs` is on and the proof repeats the other
+    /// player's.
+    DuplicateProof = 24,
+    /// The contract was deployed without a Game Hub.
+    NoHubConfigured = 25,
+    /// The operation is only allowed before any proof is accepted.
+    SubmissionsRecorded = 26,
+    /// Registering would exceed `MaxVerifiers`.
+    TooManyVerifiers = 27,
+    /// No verifier is registered under the requested name.
+    UnknownVerifier = 28,
+    /// The named verifier still has live games.
+    VerifierInUse = 29,
+    /// A split was requested with a negative amount or more than 10 000 bps.
+    InvalidSplit = 30,
+    /// The game is resolved and `FrozenResolved` is on; it only accepts
+    /// reads, settlement and `reclaim_game`.
+    GameFrozen = 31,
+    /// The matchmaking queue already holds `MAX_QUEUE_LEN` players.
+    QueueFull = 32,
+    /// The player is already waiting in the matchmaking queue.
+    AlreadyQueued = 33,
+    /// The player is not waiting in the matchmaking queue.
+    NotQueued = 34,
+    /// No target is pre-registered for the next queue session.
+    TargetNotRegistered = 35,
+    /// The requested hash function is neither `keccak256` nor `sha256`.
+    UnsupportedHash = 36,
+    /// The game was started without an arbiter.
+    NoArbiter = 37,
+    /// The escalation window has passed or the game was already arbitrated.
+    EscalationClosed = 38,
+    /// The game is waiting for its arbiter's ruling.
+    UnderArbitration = 39,
+    /// The game has not been escalated to its arbiter.
+    NotUnderArbitration = 40,
+    /// `PlayersOnlyResolve` is on and the caller is not a player resolving
+    /// through `resolve_game_as`.
+    ResolutionGated = 41,
+    /// The proof and public inputs exceed `MaxVerifierInputBudget` bytes.
+    VerifierBudgetExceeded = 42,
+    /// The session's `deadline_ledger` has passed.
+    DeadlinePassed = 43,
+    /// `claim_timeout` was called on a game without a deadline, or before
+    /// its deadline passed.
+    DeadlineNotReached = 44,
+    /// `accept_admin` was called with no admin transfer proposed.
+    NoPendingAdmin = 45,
+    /// `resolve_if_verifier_paused` is disabled (see `PausedVerifierRefund`).
+    PausedRefundDisabled = 46,
+    /// The game has not been live for `get_paused_refund_delay` seconds.
+    PausedRefundTooEarly = 47,
+    /// The game's verifier does not report itself paused.
+    VerifierNotPaused = 48,
+    /// The admin has paused the contract: no new games or submissions.
+    ContractPaused = 49,
+    /// No attestation key is set (see `set_attestation_key`).
+    NoAttestationKey = 50,
+    /// No attestation has been stored for the session.
+    NoAttestation = 51,
+    /// Only one player has verified and the `SecondPlayerWindow` opened by
+    /// that verification has not closed yet.
+    SecondPlayerWindowOpen = 52,
+    /// Currency `decimals` above `MAX_CURRENCY_DECIMALS`, or a `symbol`
+    /// that is empty or longer than `MAX_CURRENCY_SYMBOL_LEN` bytes.
+    InvalidCurrency = 53,
+    /// Committed points are not positive, or exceed `MAX_POINTS_CEILING`.
+    InvalidPoints = 54,
+    /// A player has already forfeited this game.
+    AlreadyForfeited = 55,
+    /// The proof is longer than `get_max_proof_len` bytes.
+    ProofTooLarge = 56,
+    /// A player can still `escalate` the game to its arbiter.
+    EscalationOpen = 57,
+    /// The game TTL is below one day or above the network's maximum entry
+    /// TTL.
+    InvalidTtl = 58,
+    /// The Game Hub trapped while settling this session.  A `resolve_many`
+    /// batch leaves the session unresolved and carries on.
+    HubCallFailed = 59,
+    /// A session name that is empty or longer than `MAX_SESSION_NAME_LEN`
+    /// bytes.
+    InvalidName = 60,
+    /// The session id is at or above `QUEUE_SESSION_BASE`, which only
+    /// `join_queue` may allocate.
+    ReservedSessionId = 61,
+    /// `record_rejected_inputs` was given more than
+    /// `MAX_REJECTED_INPUTS_LEN` bytes.
+    InputsTooLarge = 62,
+    /// The `MAX_SESSION_ID_SCAN` ids after the allocation counter all hold
+    /// a game, or run into the `join_queue` range.
+    NoFreeSessionId = 63,
 }
 
 // ============================================================================
@@ -107,8 +321,8 @@ pub enum Error {
 
 /// Outcome returned by `resolve_game`.
 ///
-/// Stored as a return value only — NOT stored inside `Game` to avoid nested
-/// `#[contracttype]` enum serialisation issues with Soroban SDK.
+/// `Game` stores only an arbiter's ruling (`arbitrated_outcome`); every
+/// other outcome is recomputed from the game's state.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Outcome {
@@ -116,12 +330,68 @@ pub enum Outcome {
     Player1Won,
     /// Player 2 found the treasure and used less energy.
     Player2Won,
-    /// Both found the treasure, but neither wins outright via energy (tie resolved to Player1).
+    /// Both found the treasure with equal energy; settled as a draw.
     BothFoundTreasure,
     /// Neither player provided a valid proof.
     NeitherFound,
 }
 
+/// Legacy policy for a `BothFoundTreasure` energy tie.
+///
+/// Every tie is now a draw: the Game Hub is told outcome 2, nobody is
+/// recorded as winner, and neither claims nor stake-aware settlement favour
+/// either player.  The setting is still stored and snapshotted onto each
+/// game for compatibility, but no longer affects settlement.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum TieDefault {
+    /// Formerly: player 1 won the tie.
+    #[default]
+    Player1,
+    /// Formerly: player 2 won the tie.
+    Player2,
+    /// Nobody wins; now the behaviour for every policy.
+    Draw,
+    /// Formerly: player 1 won if the first byte of
+    /// `keccak256(session_id_be ‖ treasure_hash)` was even, player 2 otherwise.
+    RandomFromSeed,
+}
+
+/// How the verifier signals an invalid proof.
+///
+/// `Inherit` is only meaningful in `GameOptions`, where it selects the global
+/// mode; stored games always carry `Trap` or `Bool`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum VerifierMode {
+    /// Use the global `DataKey::VerifierMode`.
+    #[default]
+    Inherit,
+    /// The verifier traps, reverting the whole transaction.
+    Trap,
+    /// The verifier returns `false`; `submit_zk_proof` returns `InvalidProof`.
+    Bool,
+}
+
+/// How a session's winner is decided.
+///
+/// `Inherit` is only meaningful in `GameOptions`, where it selects
+/// `DataKey::DefaultGameMode`; stored games always carry a concrete mode.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum GameMode {
+    /// Use the global `DataKey::DefaultGameMode`.
+    #[default]
+    Inherit,
+    /// Both players may verify; lower `energy_used` wins; a tie is a draw.
+    LowestEnergy,
+    /// The first verified proof wins and resolves the game immediately.
+    FirstWins,
+    /// Both players may verify; the earlier verification (by ledger, then
+    /// timestamp) wins, with energy deciding a same-ledger tie.
+    EarliestVerified,
+}
+
 /// Per-session game state stored in temporary storage.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -142,6 +412,381 @@ pub struct Game {
     pub player2_energy: Option<u32>,
     /// True after `resolve_game` has been called.  Blocks late submissions.
     pub resolved: bool,
+    /// Maximum accepted `submit_zk_proof` calls for this session, snapshotted
+    /// from `DataKey::MaxSubmissions` at `start_game`.
+    pub max_submissions: u32,
+    /// Accepted `submit_zk_proof` calls so far.
+    pub submissions: u32,
+    /// Category the session was started under; selects the Game Hub that
+    /// locks and settles its points (see `DataKey::CategoryHubs`).
+    pub category: Option<Symbol>,
+    /// Ledger timestamp at `start_game`.
+    pub started_at: u64,
+    /// Session nullifier derived at `start_game` (see Nullifier Design).
+    pub nullifier: BytesN<32>,
+    /// Nullifier derivation used for this session: `DERIVATION_V0` (untagged)
+    /// or `DERIVATION_V1` (domain-separated).
+    pub derivation_version: u32,
+    /// Seconds after `started_at` during which proofs are accepted; `None`
+    /// means no limit.
+    pub time_limit_seconds: Option<u64>,
+    /// `public_inputs` of player 1's last `PublicInputMismatch`, recorded
+    /// by `record_rejected_inputs` only while `DataKey::DebugMode` is on.
+    pub player1_rejected_inputs: Option<Bytes>,
+    /// `public_inputs` of player 2's last `PublicInputMismatch`, recorded
+    /// by `record_rejected_inputs` only while `DataKey::DebugMode` is on.
+    pub player2_rejected_inputs: Option<Bytes>,
+    /// Snapshot of `DataKey::TieDefault`; kept for compatibility, ties are
+    /// always draws.
+    pub tie_default: TieDefault,
+    /// How this session's verifier reports failure: the `GameOptions`
+    /// override, else `DataKey::VerifierMode` at start.
+    pub verifier_mode: VerifierMode,
+    /// How the winner is decided: the `GameOptions` override, else
+    /// `DataKey::DefaultGameMode` at start.
+    pub mode: GameMode,
+    /// Named verifier from `GameOptions`; `None` uses the default verifier.
+    pub verifier: Option<Symbol>,
+    /// Verifier contract pinned by `GameOptions`; takes precedence over
+    /// `verifier` and the default verifier.
+    pub verifier_override: Option<Address>,
+    /// Game Hub that locked this session's points and will settle it,
+    /// snapshotted at start (see `migrate_all_games_to_hub`); `None` on a
+    /// standalone deployment.
+    pub hub: Option<Address>,
+    /// Ledger sequence at which the game was resolved; `None` while live.
+    pub resolved_ledger: Option<u32>,
+    /// Ledger timestamp at resolution; `None` while live.
+    pub resolved_at: Option<u64>,
+    /// Lock id returned by the hub's `start_game`, or 0 for hubs that return
+    /// nothing.  Passed back via `end_game_with_outcome_and_lock`,
+    /// `settle_with_stakes_and_lock` or `refund_game_with_lock`.
+    pub hub_lock_id: u64,
+    /// Session id the hub knows this game by: the id it was started under,
+    /// kept across `rekey_game`.
+    pub hub_session_id: u32,
+    /// True while a resolved game waits for its winner's `claim_winnings`
+    /// before the hub is notified (see `DataKey::ClaimRequired`).
+    pub awaiting_claim: bool,
+    /// Last ledger the game entry is guaranteed to live until, as set by the
+    /// TTL extensions at start and on each submission.
+    pub live_until_ledger: u32,
+    /// `keccak256` of the first accepted proof, checked against the second
+    /// player's under `DataKey::RequireDistinctProofs`.
+    pub first_proof_hash: Option<BytesN<32>>,
+    /// Ledger sequence of the first accepted proof; opens the
+    /// `DataKey::SecondPlayerWindow`.
+    pub first_verified_ledger: Option<u32>,
+    /// `keccak256` of player 1's accepted proof.
+    pub player1_proof_hash: Option<BytesN<32>>,
+    /// `keccak256` of player 2's accepted proof.
+    pub player2_proof_hash: Option<BytesN<32>>,
+    /// Ledger sequence at which player 1's proof was accepted.
+    pub player1_verified_ledger: Option<u32>,
+    /// Ledger timestamp at which player 1's proof was accepted.
+    pub player1_verified_at: Option<u64>,
+    /// Ledger sequence at which player 2's proof was accepted.
+    pub player2_verified_ledger: Option<u32>,
+    /// Ledger timestamp at which player 2's proof was accepted.
+    pub player2_verified_at: Option<u64>,
+    /// True once `GameExpired` has been published for this session.
+    pub expiry_announced: bool,
+    /// Off-chain arbiter from `GameOptions`; `None` disables `escalate`.
+    pub arbiter: Option<Address>,
+    /// Last ledger accepting proofs, from `GameOptions`; `None` means no
+    /// ledger deadline.
+    pub deadline_ledger: Option<u32>,
+    /// True between `escalate` and the arbiter's `arbitrate` ruling.
+    pub under_arbitration: bool,
+    /// True once the arbiter has ruled via `arbitrate`.
+    pub arbitrated: bool,
+    /// Outcome ruled by the arbiter, replacing the energy-derived one;
+    /// meaningful only when `arbitrated`.
+    pub arbitrated_outcome: Outcome,
+    /// Decimals of the token the stakes are denominated in, from
+    /// `GameOptions`; display metadata only.
+    pub currency_decimals: Option<u32>,
+    /// Symbol of the token the stakes are denominated in, from
+    /// `GameOptions`; display metadata only.
+    pub currency_symbol: Option<Bytes>,
+    /// Submissions by player 1 after resolution, counted under
+    /// `LateSubmitPolicy::Record`.
+    pub player1_late_attempts: u32,
+    /// Submissions by player 2 after resolution, counted under
+    /// `LateSubmitPolicy::Record`.
+    pub player2_late_attempts: u32,
+    /// Player who conceded via `forfeit`; the opponent wins on resolution.
+    pub forfeited_by: Option<Address>,
+    /// Resolve on the second verification even with the global
+    /// `AutoResolve` off.
+    pub auto_resolve: bool,
+    /// Name bound by `start_game_named`, released when the game is deleted.
+    pub name: Option<Bytes>,
+}
+
+/// Optional settings accepted by `start_game_with_options`.
+///
+/// Every field defaults to "not set", which reproduces plain `start_game`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GameOptions {
+    /// Category whose hub handles this session; unknown or absent categories
+    /// fall back to the default hub.
+    pub category: Option<Symbol>,
+    /// Wall-clock submission window in seconds, measured from the start
+    /// timestamp and enforced by `submit_zk_proof`.
+    pub time_limit_seconds: Option<u64>,
+    /// Per-game override of the global `VerifierMode`; `Inherit` keeps the
+    /// global mode.
+    pub verifier_mode: VerifierMode,
+    /// Per-game override of the global `DefaultGameMode`; `Inherit` keeps the
+    /// global mode.
+    pub mode: GameMode,
+    /// Registered verifier (see `register_verifier`) that checks this game's
+    /// proofs; `None` uses the default verifier.
+    pub verifier: Option<Symbol>,
+    /// Verifier contract that checks this game's proofs instead of `verifier`
+    /// or the default verifier, e.g. to trial a new circuit.
+    pub verifier_override: Option<Address>,
+    /// Arbiter players can `escalate` the resolved game to.
+    pub arbiter: Option<Address>,
+    /// Last ledger accepting proofs; afterwards anyone can `claim_timeout`.
+    pub deadline_ledger: Option<u32>,
+    /// Decimals of the stake token, at most `MAX_CURRENCY_DECIMALS`.
+    pub currency_decimals: Option<u32>,
+    /// Symbol of the stake token, 1 to `MAX_CURRENCY_SYMBOL_LEN` bytes.
+    pub currency_symbol: Option<Bytes>,
+    /// Resolve as soon as both players have verified, as under the global
+    /// `AutoResolve`.
+    pub auto_resolve: bool,
+}
+
+/// Inputs of a session's nullifier derivation, as returned by
+/// `get_derivation_params`.
+///
+/// `nullifier = hash(preimage)` where
+/// `preimage = domain_tag ‖ session_id_be ‖ player1 strkey ‖ player2 strkey ‖
+/// contract strkey [‖ deadline_ledger_be] ‖ salt`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivationParams {
+    /// `DERIVATION_V0` or `DERIVATION_V1`.
+    pub version: u32,
+    /// Hash function applied to `preimage`: `keccak256` for every session;
+    /// `compute_target_variant` also accepts `sha256`.
+    pub hash: Symbol,
+    /// `DOMAIN_TAG` under `DERIVATION_V1`, empty under `DERIVATION_V0`.
+    pub domain_tag: Bytes,
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    /// The deriving contract, which scopes the nullifier to one deployment.
+    pub contract: Address,
+    /// The game's deadline, bound only when it has one.
+    pub deadline_ledger: Option<u32>,
+    /// Trailing salt; always empty for sessions started by this contract.
+    pub salt: Bytes,
+    /// The exact bytes hashed into the nullifier.
+    pub preimage: Bytes,
+}
+
+/// A proof being uploaded in chunks by `submit_proof_chunk`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofUpload {
+    /// Number of chunks the proof was split into.
+    pub total_chunks: u32,
+    /// Chunks received so far; the next expected `chunk_index`.
+    pub received: u32,
+    /// Concatenation of the chunks received so far.
+    pub proof: Bytes,
+}
+
+/// Number of games in each lifecycle phase, as returned by `phase_counts`.
+///
+/// Maintained incrementally on every transition.  Games that expire from
+/// temporary storage without being resolved are never decremented, and
+/// games started before the counters existed are not included.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PhaseCounts {
+    /// Live games with no verified proof yet.
+    pub open: u32,
+    /// Live games where exactly one player has verified.
+    pub one_verified: u32,
+    /// Live games where both players have verified.
+    pub both_verified: u32,
+    /// Games resolved since deployment.
+    pub resolved: u32,
+}
+
+/// A player's cumulative results, as returned by `get_stats`.
+///
+/// Counted by outcome when a game first resolves: `BothFoundTreasure` is a
+/// draw for both players and `NeitherFound` a loss for both.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlayerStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+/// Snapshot of a session for light clients, as returned by `state_proof`.
+///
+/// `commitment = keccak256(session_id_le ‖ state_hash ‖ resolved ‖
+/// outcome_code ‖ ledger_le)`, with `resolved` as one byte and
+/// `outcome_code` as in the bridge payload.  A cached proof is stale once
+/// `game_state_hash` no longer returns its `state_hash`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateProof {
+    pub session_id: u32,
+    /// `game_state_hash` when the proof was produced.
+    pub state_hash: BytesN<32>,
+    pub resolved: bool,
+    /// The resolved outcome; `NeitherFound` and meaningless while
+    /// `resolved` is false.
+    pub outcome: Outcome,
+    /// Ledger sequence the proof was produced at.
+    pub ledger: u32,
+    pub commitment: BytesN<32>,
+}
+
+/// Counts of resolved games by outcome, as returned by `stats_since`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OutcomeStats {
+    pub player1_won: u32,
+    pub player2_won: u32,
+    pub both_found: u32,
+    pub neither_found: u32,
+}
+
+/// Length aggregates of the proofs a verifier has accepted, as returned by
+/// `verifier_stats`.  All-zero for a verifier that accepted none.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifierStats {
+    /// Proofs accepted.
+    pub count: u32,
+    /// Shortest accepted proof, in bytes.
+    pub min_len: u32,
+    /// Longest accepted proof, in bytes.
+    pub max_len: u32,
+    /// Total bytes of accepted proofs; divide by `count` for the mean.
+    pub sum_len: u64,
+}
+
+/// One authorization a call will require, as returned by
+/// `start_game_auth_preview` and `resolve_auth_preview`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthRequirement {
+    /// Account that must authorize (mux ids stripped).
+    pub address: Address,
+    /// Arguments the authorization is bound to.
+    pub args: Vec<Val>,
+}
+
+/// Storage footprint estimate returned by `storage_report`.
+///
+/// Built from `PhaseCounts` and index lengths, never by scanning games, so
+/// it inherits their caveats: expired or reclaimed games are still counted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageReport {
+    /// Started, unresolved games.
+    pub active_games: u32,
+    /// Games resolved since deployment.
+    pub resolved_games: u32,
+    /// Length of the active-session index.
+    pub active_index_len: u32,
+    /// Number of registered session names.
+    pub session_names: u32,
+    /// Upper bound on live game entries in temporary storage.
+    pub estimated_temporary_entries: u32,
+}
+
+/// Lifecycle phase of a single game, matching the buckets of `PhaseCounts`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GamePhase {
+    /// No verified proof yet.
+    Open,
+    /// Exactly one player has verified.
+    OneVerified,
+    /// Both players have verified.
+    BothVerified,
+    /// The game has been resolved.
+    Resolved,
+}
+
+/// Per-session summary returned by `get_lobby_view`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LobbyEntry {
+    pub phase: GamePhase,
+    pub treasure_hash: BytesN<32>,
+    pub player1: Address,
+    pub player2: Address,
+}
+
+/// Who receives the indivisible remainder when points are split.
+///
+/// Every split rounds one share down and gives the other party the rest, so
+/// the shares always sum to the input and no value is created.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RoundingPolicy {
+    /// The winner's share rounds down; the remainder goes to the protocol.
+    #[default]
+    ToProtocol,
+    /// The protocol's share rounds down; the remainder goes to the winner.
+    ToWinner,
+}
+
+/// How `submit_zk_proof` treats a player's submission to an already
+/// resolved game, e.g. the second proof of a `FirstWins` game.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LateSubmitPolicy {
+    /// Fail with `GameAlreadyResolved`.
+    #[default]
+    Reject,
+    /// Return `Ok(None)` without touching the game.
+    Ignore,
+    /// Return `Ok(None)` and count the attempt on the game.
+    Record,
+}
+
+/// A player waiting in the matchmaking queue.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueueEntry {
+    pub player: Address,
+    pub points: i128,
+}
+
+/// Result of `split_points`.  `winner + protocol` always equals the amount
+/// split.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsSplit {
+    pub winner: i128,
+    pub protocol: i128,
+}
+
+/// Every fee- and reward-related setting, as returned by `get_fee_config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Points credited per resolution by `resolve_game_as`.
+    pub resolution_reward: i128,
+    /// Whether only the session's players earn the resolution reward.
+    pub players_only_reward: bool,
+    /// Remainder rule applied by `split_points`.
+    pub rounding_policy: RoundingPolicy,
 }
 
 /// Storage keys.
@@ -156,10 +801,424 @@ pub enum DataKey {
     VerifierAddress,
     /// Admin address (instance storage).
     Admin,
+    /// Address proposed by `propose_admin`, awaiting `accept_admin`
+    /// (instance storage).
+    PendingAdmin,
+    /// When true, new games and submissions are rejected (instance storage).
+    Paused,
+    /// ed25519 public key result attestations must verify against
+    /// (instance storage).
+    AttestationKey,
+    /// `(outcome, signature)` attested for a resolved session (temporary
+    /// storage, same TTL as games).
+    ResultAttestation(u32),
+    /// When true, `resolve_if_verifier_paused` can refund games whose
+    /// verifier is paused (instance storage).
+    PausedVerifierRefund,
+    /// Seconds a game must have been live before
+    /// `resolve_if_verifier_paused` applies (instance storage).
+    PausedRefundDelay,
+    /// Submission cap applied to newly started sessions (instance storage).
+    MaxSubmissions,
+    /// `Map<Symbol, Address>` of category → Game Hub (instance storage).
+    CategoryHubs,
+    /// `Map<Symbol, Address>` of name → registered verifier (instance
+    /// storage, bounded by `MaxVerifiers`).
+    NamedVerifiers,
+    /// Cap on the number of named verifiers (instance storage).
+    MaxVerifiers,
+    /// Live games checked by a named verifier (persistent storage).
+    VerifierGames(Symbol),
+    /// When true, mutating calls on resolved games fail with `GameFrozen`
+    /// (instance storage).
+    FrozenResolved,
+    /// `RoundingPolicy` for `split_points` (instance storage).
+    RoundingPolicy,
+    /// Latest live session between two players, lower address first
+    /// (temporary storage, same TTL as games).
+    ActivePair(Address, Address),
+    /// Operator note recorded when a session is cancelled or force-resolved
+    /// (temporary storage, same TTL as games).
+    CancelReason(u32),
+    /// When true, only allowlisted players may start games (instance storage).
+    EligibilityRequired,
+    /// Allowlist membership for a player (persistent storage).
+    Eligible(Address),
+    /// When true, new sessions derive their nullifier with `DOMAIN_TAG`
+    /// (instance storage).
+    DomainSeparation,
+    /// Sessions in which a proof was accepted by a verifier, most recent last
+    /// (persistent storage, bounded by `MAX_VERIFIER_SESSIONS`).
+    VerifierSessions(Address),
+    /// Proof-length aggregates of a verifier's accepted proofs (persistent
+    /// storage).
+    VerifierStats(Address),
+    /// When true, settlements report both committed stakes via
+    /// `settle_with_stakes` (instance storage).
+    StakeAwareSettlement,
+    /// When true, `submit_zk_proof` resolves a game once both players have
+    /// verified (instance storage).
+    AutoResolve,
+    /// Session id bound to a name by `start_game_named` (persistent
+    /// storage, removed with the game).
+    SessionName(Bytes),
+    /// Number of `SessionName` entries (instance storage).
+    SessionNameCount,
+    /// Sessions a player has started, oldest first (persistent storage,
+    /// bounded by `MaxPlayerSessions`).
+    PlayerSessions(Address),
+    /// Cap on each player's session index (instance storage).
+    MaxPlayerSessions,
+    /// When true, `record_rejected_inputs` stores a player's mismatched
+    /// `public_inputs` on the game; `submit_zk_proof` still fails with
+    /// `PublicInputMismatch` either way (instance storage).
+    DebugMode,
+    /// `TieDefault` snapshotted onto sessions started from now on; no
+    /// longer affects settlement (instance storage).
+    TieDefault,
+    /// A player's in-progress chunked proof for a session (temporary
+    /// storage, same TTL as games).
+    ProofChunks(u32, Address),
+    /// `VerifierMode` applied to sessions started without an override
+    /// (instance storage).
+    VerifierMode,
+    /// `GameMode` for sessions started without an explicit mode (instance
+    /// storage; `LowestEnergy` when unset).
+    DefaultGameMode,
+    /// `PhaseCounts` snapshot (instance storage).
+    PhaseCounts,
+    /// `(session_id, won)` for a player's most recent resolutions, oldest
+    /// first (persistent storage, bounded by `MAX_PLAYER_RESULTS`).
+    PlayerResults(Address),
+    /// Cumulative `PlayerStats` of a player (persistent storage).
+    Stats(Address),
+    /// `OutcomeStats` of games resolved in one `OUTCOME_BUCKET_LEDGERS`
+    /// bucket, keyed by bucket index (persistent storage).
+    OutcomeBucket(u32),
+    /// `LateSubmitPolicy` for submissions to resolved games (instance
+    /// storage).
+    LateSubmitPolicy,
+    /// Number of entries in the active-session index: started, unresolved
+    /// sessions, including any that expired unresolved (instance storage).
+    ActiveSessionCount,
+    /// Session id at a position of the active-session index (persistent
+    /// storage).  Removing an entry moves the last one into its position.
+    ActiveSessionAt(u32),
+    /// Position of a session in the active-session index (persistent
+    /// storage).
+    ActiveSessionSlot(u32),
+    /// Ledgers that must pass after a session resolves before its id can be
+    /// started again (instance storage).
+    SessionReuseCooldown,
+    /// When true, submissions extend the game's TTL in proportion to its
+    /// remaining submission budget (instance storage).
+    ProportionalTtl,
+    /// Ledgers game entries (and their per-session side entries) are kept
+    /// alive for (instance storage).
+    GameTtl,
+    /// Cap on the points each player may commit; unset means no cap
+    /// (instance storage).
+    MaxPoints,
+    /// Admin-granted points cap for one session id, replacing `MaxPoints`
+    /// when it starts (temporary storage, same TTL as games).
+    GameMaxPoints(u32),
+    /// Admin-committed treasure hash adopted by the session when it starts,
+    /// in place of the caller's (temporary storage, same TTL as games).
+    PreregisteredTarget(u32),
+    /// Current id of a game `rekey_game` moved away from the hub session id
+    /// it still settles under (persistent storage).  Keeps that id from
+    /// being started again while the game exists.
+    HubSessionMoved(u32),
+    /// When true, the second player's proof must differ from the first's
+    /// (instance storage).
+    RequireDistinctProofs,
+    /// When true, an exact retry of an accepted submission succeeds as a
+    /// no-op (instance storage).
+    IdempotentSubmit,
+    /// When true, games with a winner settle on the hub only once the winner
+    /// claims (instance storage).
+    ClaimRequired,
+    /// Ledgers after resolution during which only the winner can settle an
+    /// unclaimed game (instance storage).
+    ClaimWindow,
+    /// When true, `reclaim_game` keeps the outcome under `ReclaimedOutcome`
+    /// (instance storage).
+    KeepOutcomeAfterReclaim,
+    /// Outcome of a reclaimed game (temporary storage).
+    ReclaimedOutcome(u32),
+    /// Points credited to whoever resolves a game via `resolve_game_as`
+    /// (instance storage).
+    ResolutionReward,
+    /// When true, only players earn the resolution reward (instance storage).
+    PlayersOnlyReward,
+    /// When true, only a player resolving through `resolve_game_as` can
+    /// resolve a game (instance storage).
+    PlayersOnlyResolve,
+    /// Cap on the bytes (proof plus public inputs) handed to the verifier
+    /// per submission (instance storage).
+    MaxVerifierInputBudget,
+    /// Longest proof, in bytes, accepted by `submit_zk_proof` (instance
+    /// storage).
+    MaxProofLen,
+    /// Resolution rewards credited to an account (persistent storage).
+    RewardBalance(Address),
+    /// Players waiting for a match, oldest first (persistent storage).
+    MatchQueue,
+    /// Session id assigned to the next queue match (instance storage).
+    NextQueueSession,
+    /// Lower bound for the next id `start_game_auto` allocates (instance
+    /// storage).
+    NextSessionId,
+    /// When true, the queue only matches players committing equal points
+    /// (instance storage).
+    RequireEqualPoints,
+    /// Ledgers after resolution during which a player can `escalate`
+    /// (instance storage).
+    EscalationWindow,
+    /// Ledgers after the first verification during which the game cannot
+    /// be resolved, so the opponent can still respond (instance storage).
+    SecondPlayerWindow,
+    /// Storage schema version the instance was last migrated to (instance
+    /// storage); absent on instances deployed before versioning.
+    SchemaVersion,
+}
+
+/// Version of this contract code, and of the storage schema it expects.
+pub const VERSION: u32 = 1;
+
+/// Default game TTL: 30 days = 30 × 24 × 3600 / 5 ≈ 518 400 ledgers
+/// (5-second ledger close).
+const DEFAULT_GAME_TTL_LEDGERS: u32 = 518_400;
+
+/// Shortest game TTL `set_game_ttl` accepts: 1 day.
+const MIN_GAME_TTL_LEDGERS: u32 = 17_280;
+
+/// Nominal ledger close time, used to translate wall-clock limits into
+/// ledger estimates.
+const SECONDS_PER_LEDGER: u64 = 5;
+
+/// Default claim window: 7 days of 5-second ledgers.
+const DEFAULT_CLAIM_WINDOW_LEDGERS: u32 = 120_960;
+
+/// Default age a game must reach before a paused verifier lets it be
+/// refunded: 1 hour.
+const DEFAULT_PAUSED_REFUND_DELAY_SECONDS: u64 = 3_600;
+
+/// Default escalation window: 1 day of 5-second ledgers.
+const DEFAULT_ESCALATION_WINDOW_LEDGERS: u32 = 17_280;
+
+/// Absolute ceiling for any points cap, global or per game.
+pub const MAX_POINTS_CEILING: i128 = 1_000_000_000_000_000;
+
+/// Floor of the proportional submission TTL: 1 day, enough to resolve a
+/// game whose submission budget is spent.
+const MIN_SUBMISSION_TTL_LEDGERS: u32 = 17_280;
+
+/// Default per-session submission cap: one accepted proof per player.
+const DEFAULT_MAX_SUBMISSIONS: u32 = 2;
+
+/// Default cap on proof length: 32 KiB, about twice an UltraHonk proof.
+const DEFAULT_MAX_PROOF_LEN: u32 = 32 * 1024;
+
+/// Maximum number of addresses accepted by a single eligibility batch call.
+const MAX_ELIGIBILITY_BATCH: u32 = 50;
+
+/// Number of active-index entries visited by one `migrate_all_games_to_hub`
+/// call; keeps each call within the per-transaction ledger entry limits.
+const MAX_MIGRATION_BATCH: u32 = 25;
+
+/// Maximum number of sessions accepted by a single `get_lobby_view` call.
+const MAX_LOBBY_BATCH: u32 = 50;
+
+/// Maximum number of sessions accepted by a single `resolve_many` call.
+const MAX_RESOLVE_BATCH: u32 = 50;
+
+/// Maximum number of sessions accepted by a single `prune_expired` call.
+const MAX_PRUNE_BATCH: u32 = 50;
+
+/// Width of an outcome-statistics bucket: 10 000 ledgers, about 14 hours
+/// of 5-second ledgers.
+pub const OUTCOME_BUCKET_LEDGERS: u32 = 10_000;
+
+/// Buckets `stats_since` reads at most, ending with the current one:
+/// about 29 days.
+pub const MAX_STATS_BUCKETS: u32 = 50;
+
+/// Basis points in a whole.
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Default cap on the number of named verifiers.
+const DEFAULT_MAX_VERIFIERS: u32 = 10;
+
+/// Maximum number of players waiting in the matchmaking queue.
+const MAX_QUEUE_LEN: u32 = 50;
+
+/// First session id assigned to queue matches; ids from here up are
+/// reserved for `join_queue`, and every other start path and `rekey_game`
+/// reject them with `ReservedSessionId`.
+pub const QUEUE_SESSION_BASE: u32 = 0x8000_0000;
+
+/// Number of most recent active sessions scanned by `find_session_by_target`.
+const MAX_TARGET_SCAN: u32 = 50;
+
+/// Number of ids `peek_next_session_id` checks before giving up.
+const MAX_SESSION_ID_SCAN: u32 = 50;
+
+/// Number of sessions remembered per verifier by `games_verified_by`.
+const MAX_VERIFIER_SESSIONS: u32 = 100;
+
+/// Largest stake-token `decimals` accepted by `start_game_with_options`.
+pub const MAX_CURRENCY_DECIMALS: u32 = 18;
+
+/// Longest stake-token symbol accepted by `start_game_with_options`.
+pub const MAX_CURRENCY_SYMBOL_LEN: u32 = 12;
+
+/// Longest session name accepted by `start_game_named`.
+pub const MAX_SESSION_NAME_LEN: u32 = 32;
+
+/// Longest `public_inputs` accepted by `record_rejected_inputs`.
+pub const MAX_REJECTED_INPUTS_LEN: u32 = 256;
+
+/// Number of resolutions remembered per player by `player_results`.
+const MAX_PLAYER_RESULTS: u32 = 50;
+
+/// Default cap on each player's session index.
+const DEFAULT_MAX_PLAYER_SESSIONS: u32 = 100;
+
+/// Domain-separation tag prepended to the nullifier preimage under
+/// `DERIVATION_V1`.
+pub const DOMAIN_TAG: &[u8] = b"EATHER-GRID-V1";
+
+/// Untagged nullifier derivation:
+/// `keccak256(session_id ‖ player1 ‖ player2 ‖ contract_address [‖ deadline_ledger])`.
+pub const DERIVATION_V0: u32 = 0;
+
+/// Domain-separated derivation:
+/// `keccak256(DOMAIN_TAG ‖ session_id ‖ player1 ‖ player2 ‖ contract_address [‖ deadline_ledger])`.
+pub const DERIVATION_V1: u32 = 1;
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Published when a player's proof is accepted.
+///
+/// Besides informing indexers, this ensures the Soroban SDK recognizes
+/// `submit_zk_proof` as a state-mutating transaction instead of silently
+/// skipping submission in its `isStillReadOnly` fallback.
+#[contractevent(topics = ["submit"], data_format = "single-value")]
+pub struct ProofSubmitted {
+    #[topic]
+    pub session_id: u32,
+    pub energy_used: u32,
+}
+
+/// Published when the admin force-resolves a session.
+#[contractevent(topics = ["force_resolved"])]
+pub struct GameForceResolved {
+    #[topic]
+    pub session_id: u32,
+    pub outcome: Outcome,
+    pub reason: Option<Symbol>,
+}
+
+/// Published when both players cancel a session nobody has verified.
+#[contractevent(topics = ["game", "cancelled"])]
+pub struct GameCancelled {
+    #[topic]
+    pub session_id: u32,
+    pub hub_session_id: u32,
+    pub reason: Option<Symbol>,
+}
+
+/// Published by `close_session` just before a resolved game is deleted,
+/// so indexers keep its final outcome.
+#[contractevent(topics = ["game", "closed"], data_format = "single-value")]
+pub struct GameClosed {
+    #[topic]
+    pub session_id: u32,
+    pub outcome: Outcome,
+}
+
+/// Published when the admin refunds a stuck session.
+#[contractevent(topics = ["admin_refund"], data_format = "single-value")]
+pub struct GameRefunded {
+    #[topic]
+    pub session_id: u32,
+    pub admin: Address,
+}
+
+/// Published by every `start_game`, so indexers can follow games without
+/// polling.  Data: `[player1, player2, treasure_hash]`.
+#[contractevent(topics = ["game", "started"], data_format = "vec")]
+pub struct GameStarted {
+    #[topic]
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub treasure_hash: BytesN<32>,
+}
+
+/// Published on every accepted proof.  Data: `[player, is_player1]`.
+#[contractevent(topics = ["game", "submitted"], data_format = "vec")]
+pub struct GameSubmitted {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub is_player1: bool,
+}
+
+/// Published once per session, by the call that resolves it.
+#[contractevent(topics = ["game", "resolved"], data_format = "single-value")]
+pub struct GameResolved {
+    #[topic]
+    pub session_id: u32,
+    pub outcome: Outcome,
+}
+
+/// Published by `start_game` for sessions with a time limit or deadline
+/// ledger, carrying the
+/// `effective_deadline` ledger so indexers can schedule reminders.
+#[contractevent(topics = ["game", "deadline"], data_format = "single-value")]
+pub struct GameDeadline {
+    #[topic]
+    pub session_id: u32,
+    pub deadline_ledger: u32,
+}
+
+/// Published once per session, by the first resolution that observes the
+/// time limit or deadline ledger has passed.
+#[contractevent(topics = ["game", "expired"], data_format = "single-value")]
+pub struct GameExpired {
+    #[topic]
+    pub session_id: u32,
+    pub ledger: u32,
+}
+
+/// Published when a player escalates a resolved game to its arbiter.
+#[contractevent(topics = ["escalated"])]
+pub struct GameEscalated {
+    #[topic]
+    pub session_id: u32,
+    #[topic]
+    pub arbiter: Address,
+    pub player: Address,
+}
+
+/// Published when the arbiter rules on an escalated game.
+#[contractevent(topics = ["arbitrated"], data_format = "single-value")]
+pub struct GameArbitrated {
+    #[topic]
+    pub session_id: u32,
+    pub outcome: Outcome,
 }
 
-/// 30 days = 30 × 24 × 3600 / 5 ≈ 518 400 ledgers (5-second ledger close).
-const GAME_TTL_LEDGERS: u32 = 518_400;
+/// Published on every resolution with the fixed-layout payload described
+/// under Bridge Event, for relayers bridging results to other chains.
+#[contractevent(topics = ["bridge", "result"], data_format = "single-value")]
+pub struct BridgeResult {
+    pub payload: Bytes,
+}
 
 // ============================================================================
 // Contract
@@ -178,16 +1237,46 @@ impl EatherGridContract {
     ///
     /// # Arguments
     /// * `admin`    – Admin address (`set_*` + `upgrade`).
-    /// * `game_hub` – Address of the mock-game-hub contract.
+    /// * `game_hub` – Address of the mock-game-hub contract, or `None` for a
+    ///                standalone deployment that never calls a hub.
     /// * `verifier` – Address of the deployed UltraHonk verifier.
-    pub fn __constructor(env: Env, admin: Address, game_hub: Address, verifier: Address) {
+    ///
+    /// Panics unless the three addresses are mutually distinct, since any
+    /// overlap is a misconfiguration.  The stored schema version marks the
+    /// instance initialized, so the constructor cannot run twice.
+    pub fn __constructor(env: Env, admin: Address, game_hub: Option<Address>, verifier: Address) {
+        if env.storage().instance().has(&DataKey::SchemaVersion) {
+            panic!("Contract already initialized");
+        }
+        if admin == verifier {
+            panic!("Admin and verifier must differ");
+        }
+        if let Some(hub) = &game_hub {
+            if *hub == admin {
+                panic!("Admin and game hub must differ");
+            }
+            if *hub == verifier {
+                panic!("Game hub and verifier must differ");
+            }
+        }
+        if !Self::is_wasm_contract(&verifier) {
+            panic_with_error!(&env, Error::NotAContract);
+        }
         env.storage().instance().set(&DataKey::Admin, &admin);
+        if let Some(game_hub) = game_hub {
+            env.storage()
+                .instance()
+                .set(&DataKey::GameHubAddress, &game_hub);
+        }
         env.storage()
             .instance()
-            .set(&DataKey::GameHubAddress, &game_hub);
+            .set(&DataKey::VerifierAddress, &verifier);
         env.storage()
             .instance()
-            .set(&DataKey::VerifierAddress, &verifier);
+            .set(&DataKey::GameTtl, &DEFAULT_GAME_TTL_LEDGERS);
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &VERSION);
     }
 
     // ========================================================================
@@ -199,340 +1288,4301 @@ impl EatherGridContract {
     /// The frontend must supply `treasure_hash` = `pedersen_hash([x, y, nullifier])`
     /// where `nullifier` is derived from session identity to prevent replay.
     ///
-    /// Recommended nullifier construction (off-chain):
-    ///   `nullifier = keccak256(session_id_be ‖ player1_bytes ‖ player2_bytes)`
+    /// Obtain the nullifier from `derive_nullifier` before calling; the same
+    /// value is derived and stored on the game (see Nullifier Design).
     ///
     /// # Arguments
     /// * `session_id`     – Unique session identifier (u32).
-    /// * `player1`        – First player's address.
-    /// * `player2`        – Second player's address.
+    /// * `player1`        – First player's address (a muxed address is
+    ///                      normalized to its underlying account).
+    /// * `player2`        – Second player's address (normalized likewise).
     /// * `player1_points` – Points committed by player 1.
     /// * `player2_points` – Points committed by player 2.
     /// * `treasure_hash`  – Pedersen hash of the session's canonical coordinates.
+    ///
+    /// Returns `SessionExists` while an unresolved game holds `session_id`,
+    /// and `ClaimPending`, `UnderArbitration` or `EscalationOpen` while its
+    /// resolved game awaits the winner's claim or the arbiter; other resolved
+    /// or expired ids can be reused.  Ids from `QUEUE_SESSION_BASE` up are
+    /// reserved for `join_queue` (`ReservedSessionId`).  Both point amounts
+    /// must be positive and at most `MAX_POINTS_CEILING` (`InvalidPoints`).
     pub fn start_game(
         env: Env,
         session_id: u32,
-        player1: Address,
-        player2: Address,
+        player1: MuxedAddress,
+        player2: MuxedAddress,
         player1_points: i128,
         player2_points: i128,
         treasure_hash: BytesN<32>,
     ) -> Result<(), Error> {
-        if player1 == player2 {
-            panic!("Cannot play against yourself");
-        }
+        Self::start_game_with_options(
+            env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            treasure_hash,
+            GameOptions::default(),
+        )
+    }
 
-        // Both players must authorise their point commitment for this session.
-        player1.require_auth_for_args(vec![
-            &env,
-            session_id.into_val(&env),
-            player1_points.into_val(&env),
-        ]);
-        player2.require_auth_for_args(vec![
+    /// Describe the authorizations `start_game` (and its `_with_options` /
+    /// `_named` variants) will require for these arguments, in call order,
+    /// so wallets can pre-authorize delegated or multi-sig flows.
+    ///
+    /// Each entry is bound to the invoked start function of this contract.
+    /// Pure computation: nothing is validated.
+    pub fn start_game_auth_preview(
+        env: Env,
+        session_id: u32,
+        player1: MuxedAddress,
+        player2: MuxedAddress,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Vec<AuthRequirement> {
+        vec![
             &env,
-            session_id.into_val(&env),
-            player2_points.into_val(&env),
-        ]);
+            AuthRequirement {
+                address: player1.address(),
+                args: Self::start_auth_args(&env, session_id, player1_points),
+            },
+            AuthRequirement {
+                address: player2.address(),
+                args: Self::start_auth_args(&env, session_id, player2_points),
+            },
+        ]
+    }
 
-        // Register the session with the Game Hub (locks points).
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+    /// Start a new game and bind a human-readable `name` to its session id.
+    ///
+    /// Identical to `start_game` otherwise.  Look the id up later with
+    /// `resolve_session_name`.  The name is released when the game is
+    /// deleted, or once its game has expired.
+    ///
+    /// # Errors
+    /// * `InvalidName` – `name` is empty or longer than
+    ///   `MAX_SESSION_NAME_LEN` bytes.
+    /// * `NameTaken` – `name` is already bound to a session.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_game_named(
+        env: Env,
+        name: Bytes,
+        session_id: u32,
+        player1: MuxedAddress,
+        player2: MuxedAddress,
+        player1_points: i128,
+        player2_points: i128,
+        treasure_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        if name.is_empty() || name.len() > MAX_SESSION_NAME_LEN {
+            return Err(Error::InvalidName);
+        }
+        if Self::named_session(&env, &name).is_some() {
+            return Err(Error::NameTaken);
+        }
 
-        let game = Game {
+        Self::start_game(
+            env.clone(),
+            session_id,
             player1,
             player2,
             player1_points,
             player2_points,
             treasure_hash,
-            player1_energy: None,
-            player2_energy: None,
-            resolved: false,
-        };
+        )?;
 
-        let key = DataKey::Game(session_id);
-        env.storage().temporary().set(&key, &game);
+        let mut game = Self::load_game(&env, session_id)?;
+        game.name = Some(name.clone());
         env.storage()
             .temporary()
-            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
-
+            .set(&DataKey::Game(session_id), &game);
+        let key = DataKey::SessionName(name);
+        if !env.storage().persistent().has(&key) {
+            Self::update_session_name_count(&env, |count| count + 1);
+        }
+        env.storage().persistent().set(&key, &session_id);
         Ok(())
     }
 
-    /// Submit a ZK proof of treasure discovery.
-    ///
-    /// # Responsibilities
-    /// 1. Validates `public_inputs == game.treasure_hash` (opaque 32-byte
-    ///    comparison — no byte slicing, no field parsing).
-    /// 2. Cross-contract call to the UltraHonk verifier.  If the proof is
-    ///    invalid the verifier traps, reverting the entire transaction.
-    /// 3. Records `energy_used` for the player on success.
-    ///
-    /// # Replay Protection
-    /// - `AlreadySubmitted` prevents a player from submitting twice.
-    /// - `PublicInputMismatch` blocks cross-session proof reuse because each
-    ///   session's `treasure_hash` embeds a unique session-bound nullifier.
-    /// - `GameAlreadyResolved` blocks late submissions.
-    ///
-    /// # Security Note (energy_used)
-    /// `energy_used` is a caller-supplied `u32` in this version.  A dishonest
-    /// player can underreport it.  Future circuit versions should include
-    /// `energy_used` as a verified public output of the Noir circuit.
+    /// Start a new game with optional per-session settings.
     ///
-    /// # Arguments
-    /// * `session_id`    – Session being submitted to.
-    /// * `player`        – Submitting player (must be player1 or player2).
-    /// * `proof`         – Raw UltraHonk proof bytes (opaque).
-    /// * `public_inputs` – Must equal `game.treasure_hash`.
-    /// * `energy_used`   – Energy the player claims to have spent reaching the
-    ///                     treasure (lower = better for the tiebreaker).
-    pub fn submit_zk_proof(
+    /// Identical to `start_game` except for `options`; see [`GameOptions`]
+    /// for the available settings.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_game_with_options(
         env: Env,
         session_id: u32,
-        player: Address,
-        proof: Bytes,
-        public_inputs: Bytes,
-        energy_used: u32,
-    ) -> Result<(), Error> {
-        player.require_auth();
+        player1: MuxedAddress,
+        player2: MuxedAddress,
+        player1_points: i128,
+        player2_points: i128,
+        treasure_hash: BytesN<32>,
+        options: GameOptions,
+    ) -> Result<(), Error> {
+        // Strip mux ids: players are identified, authorized and bound into
+        // the nullifier by their underlying account.
+        Self::start_session(
+            &env,
+            session_id,
+            player1.address(),
+            player2.address(),
+            player1_points,
+            player2_points,
+            treasure_hash,
+            options,
+            true,
+        )
+    }
+
+    /// Start a game under the next free session id and return that id.
+    ///
+    /// The id is `peek_next_session_id`: ids are allocated in increasing
+    /// order, skipping any that already hold a game.  Since the nullifier
+    /// and the players' auth both bind the session id, clients read
+    /// `peek_next_session_id` first and build `treasure_hash` and the auth
+    /// entries for it.  Otherwise identical to `start_game`, plus
+    /// `NoFreeSessionId` when `peek_next_session_id` finds no free id.
+    pub fn start_game_auto(
+        env: Env,
+        player1: MuxedAddress,
+        player2: MuxedAddress,
+        player1_points: i128,
+        player2_points: i128,
+        treasure_hash: BytesN<32>,
+    ) -> Result<u32, Error> {
+        let session_id = Self::peek_next_session_id(env.clone())?;
+        Self::start_game(
+            env.clone(),
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            treasure_hash,
+        )?;
+        env.storage()
+            .instance()
+            .set(&DataKey::NextSessionId, &session_id.wrapping_add(1));
+        Ok(session_id)
+    }
+
+    /// Queue `player` for a match, committing `points`, and start a game as
+    /// soon as a compatible opponent is waiting.
+    ///
+    /// The oldest waiting entry with a different player (and, under
+    /// `RequireEqualPoints`, the same points) becomes player1.  Matched
+    /// games take the session id from `next_queue_session` and their
+    /// treasure hash from `preregister_target`, which the game server must
+    /// call for that id beforehand.  Each player's `join_queue` auth stands
+    /// in for the `start_game` auth.
+    ///
+    /// `player` must pass the pause, points cap and eligibility checks
+    /// `start_game` applies before joining.  A waiting entry that no longer
+    /// passes them (the cap was lowered or its eligibility revoked) is
+    /// evicted when reached instead of failing the match.
+    ///
+    /// Returns the started session id, or `None` if `player` is now waiting.
+    ///
+    /// # Errors
+    /// * `AlreadyQueued` – `player` is already waiting.
+    /// * `InvalidPoints` – `points` is not positive or exceeds
+    ///   `MAX_POINTS_CEILING`.
+    /// * `ContractPaused` – the contract is paused.
+    /// * `PointsAboveCap` – `points` exceeds the cap for the next queue
+    ///   session.
+    /// * `NotEligible` – eligibility is required and `player` is not
+    ///   eligible.
+    /// * `QueueFull` – `MAX_QUEUE_LEN` players are already waiting.
+    /// * `TargetNotRegistered` – a match was found but no target is
+    ///   pre-registered for the next queue session.
+    /// * Any error `start_game` can return for the match.
+    pub fn join_queue(env: Env, player: Address, points: i128) -> Result<Option<u32>, Error> {
+        player.require_auth();
+        Self::check_points(points)?;
+        let mut queue = Self::match_queue(&env);
+        if queue.iter().any(|entry| entry.player == player) {
+            return Err(Error::AlreadyQueued);
+        }
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        let session_id = Self::next_queue_session(env.clone());
+        Self::check_queue_entry(&env, session_id, &player, points)?;
+
+        let equal_points = Self::is_require_equal_points(env.clone());
+        let mut opponent = None;
+        let mut index = 0;
+        while index < queue.len() {
+            let entry = queue.get_unchecked(index);
+            if equal_points && entry.points != points {
+                index += 1;
+                continue;
+            }
+            queue.remove(index);
+            if Self::check_queue_entry(&env, session_id, &entry.player, entry.points).is_ok() {
+                opponent = Some(entry);
+                break;
+            }
+        }
+        let Some(opponent) = opponent else {
+            if queue.len() >= MAX_QUEUE_LEN {
+                return Err(Error::QueueFull);
+            }
+            queue.push_back(QueueEntry { player, points });
+            env.storage().persistent().set(&DataKey::MatchQueue, &queue);
+            return Ok(None);
+        };
+
+        let target: BytesN<32> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::PreregisteredTarget(session_id))
+            .ok_or(Error::TargetNotRegistered)?;
+        env.storage().persistent().set(&DataKey::MatchQueue, &queue);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextQueueSession, &session_id.wrapping_add(1));
+
+        Self::start_session(
+            &env,
+            session_id,
+            opponent.player,
+            player,
+            opponent.points,
+            points,
+            target,
+            GameOptions::default(),
+            false,
+        )?;
+        Ok(Some(session_id))
+    }
+
+    /// Leave the matchmaking queue.
+    ///
+    /// # Errors
+    /// * `NotQueued` – `player` is not waiting.
+    pub fn leave_queue(env: Env, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        let mut queue = Self::match_queue(&env);
+        let index = queue
+            .iter()
+            .position(|entry| entry.player == player)
+            .ok_or(Error::NotQueued)?;
+        queue.remove(index as u32);
+        env.storage().persistent().set(&DataKey::MatchQueue, &queue);
+        Ok(())
+    }
+
+    /// Validate and store a new game.
+    ///
+    /// Shared by `start_game_with_options` (with player auth) and
+    /// `join_queue` (whose own auth covers both players).
+    #[allow(clippy::too_many_arguments)]
+    fn start_session(
+        env: &Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        treasure_hash: BytesN<32>,
+        options: GameOptions,
+        require_player_auth: bool,
+    ) -> Result<(), Error> {
+        let env = env.clone();
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        // Only `join_queue` starts sessions without player auth, and only it
+        // may use the queue's id range.
+        if require_player_auth && session_id >= QUEUE_SESSION_BASE {
+            return Err(Error::ReservedSessionId);
+        }
+        if player1 == player2 {
+            panic!("Cannot play against yourself");
+        }
+        if options
+            .currency_decimals
+            .is_some_and(|decimals| decimals > MAX_CURRENCY_DECIMALS)
+            || options
+                .currency_symbol
+                .as_ref()
+                .is_some_and(|symbol| symbol.is_empty() || symbol.len() > MAX_CURRENCY_SYMBOL_LEN)
+        {
+            return Err(Error::InvalidCurrency);
+        }
+        if let Ok(previous) = Self::load_game(&env, session_id) {
+            // Overwriting a live game would orphan its locked points.
+            if !previous.resolved {
+                return Err(Error::SessionExists);
+            }
+            // ... and so would dropping a winner's pending claim.
+            if previous.awaiting_claim {
+                return Err(Error::ClaimPending);
+            }
+            // ... or let `escalate`/`arbitrate` act on the new game.
+            if previous.under_arbitration {
+                return Err(Error::UnderArbitration);
+            }
+            if Self::escalation_open(&env, &previous) {
+                return Err(Error::EscalationOpen);
+            }
+            let cooldown = Self::get_session_reuse_cooldown(env.clone());
+            if let Some(resolved_ledger) = previous.resolved_ledger {
+                if env.ledger().sequence() < resolved_ledger.saturating_add(cooldown) {
+                    return Err(Error::SessionReuseTooSoon);
+                }
+            }
+        }
+        // The hub still holds this id for a game `rekey_game` moved away.
+        if Self::hub_session_moved(&env, session_id) {
+            return Err(Error::SessionExists);
+        }
+        if options
+            .deadline_ledger
+            .is_some_and(|deadline| deadline < env.ledger().sequence())
+        {
+            return Err(Error::DeadlinePassed);
+        }
+        Self::check_points(player1_points)?;
+        Self::check_points(player2_points)?;
+        if let Some(cap) = Self::points_cap_for(&env, session_id) {
+            if player1_points > cap || player2_points > cap {
+                return Err(Error::PointsAboveCap);
+            }
+        }
+        if let Some(name) = &options.verifier {
+            if !Self::named_verifiers(&env).contains_key(name.clone()) {
+                return Err(Error::UnknownVerifier);
+            }
+        }
+        if Self::is_eligibility_required(env.clone())
+            && !(Self::is_eligible(env.clone(), player1.clone())
+                && Self::is_eligible(env.clone(), player2.clone()))
+        {
+            return Err(Error::NotEligible);
+        }
+
+        // Both players must authorise their point commitment for this session.
+        if require_player_auth {
+            player1.require_auth_for_args(Self::start_auth_args(&env, session_id, player1_points));
+            player2.require_auth_for_args(Self::start_auth_args(&env, session_id, player2_points));
+        }
+
+        // Register the session with the category's Game Hub (locks points).
+        let game_hub_addr = Self::hub_for(&env, &options.category);
+        let hub_lock_id = match &game_hub_addr {
+            Some(hub) => {
+                let ack = GameHubClient::new(&env, hub).start_game(
+                    &env.current_contract_address(),
+                    &session_id,
+                    &player1,
+                    &player2,
+                    &player1_points,
+                    &player2_points,
+                );
+                u64::try_from_val(&env, &ack).unwrap_or(0)
+            }
+            None => 0,
+        };
+
+        let derivation_version = if Self::is_domain_separation_enabled(env.clone()) {
+            DERIVATION_V1
+        } else {
+            DERIVATION_V0
+        };
+        let nullifier = Self::compute_nullifier(
+            &env,
+            derivation_version,
+            session_id,
+            &player1,
+            &player2,
+            options.deadline_ledger,
+        );
+
+        let target_key = DataKey::PreregisteredTarget(session_id);
+        let treasure_hash = match env.storage().temporary().get(&target_key) {
+            Some(target) => {
+                env.storage().temporary().remove(&target_key);
+                target
+            }
+            None => treasure_hash,
+        };
+
+        let ttl = Self::get_game_ttl(env.clone());
+        let game = Game {
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            treasure_hash,
+            player1_energy: None,
+            player2_energy: None,
+            resolved: false,
+            max_submissions: Self::get_max_submissions(env.clone()),
+            submissions: 0,
+            category: options.category,
+            started_at: env.ledger().timestamp(),
+            nullifier,
+            derivation_version,
+            time_limit_seconds: options.time_limit_seconds,
+            player1_rejected_inputs: None,
+            player2_rejected_inputs: None,
+            tie_default: Self::get_tie_default(env.clone()),
+            hub: game_hub_addr,
+            resolved_ledger: None,
+            resolved_at: None,
+            hub_lock_id,
+            hub_session_id: session_id,
+            awaiting_claim: false,
+            first_proof_hash: None,
+            first_verified_ledger: None,
+            player1_proof_hash: None,
+            player2_proof_hash: None,
+            player1_verified_ledger: None,
+            player1_verified_at: None,
+            player2_verified_ledger: None,
+            player2_verified_at: None,
+            expiry_announced: false,
+            arbiter: options.arbiter,
+            deadline_ledger: options.deadline_ledger,
+            under_arbitration: false,
+            arbitrated: false,
+            arbitrated_outcome: Outcome::NeitherFound,
+            currency_decimals: options.currency_decimals,
+            currency_symbol: options.currency_symbol,
+            player1_late_attempts: 0,
+            player2_late_attempts: 0,
+            forfeited_by: None,
+            auto_resolve: options.auto_resolve,
+            live_until_ledger: env.ledger().sequence().saturating_add(ttl),
+            verifier_mode: match options.verifier_mode {
+                VerifierMode::Inherit => Self::get_verifier_mode(env.clone()),
+                mode => mode,
+            },
+            mode: match options.mode {
+                GameMode::Inherit => Self::get_default_game_mode(env.clone()),
+                mode => mode,
+            },
+            verifier: options.verifier,
+            verifier_override: options.verifier_override,
+            name: None,
+        };
+
+        let key = DataKey::Game(session_id);
+        env.storage().temporary().set(&key, &game);
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+
+        Self::record_player_session(&env, &game.player1, session_id);
+        Self::record_player_session(&env, &game.player2, session_id);
+        Self::update_phase_counts(&env, |c| c.open += 1);
+        if let Some(name) = &game.verifier {
+            Self::update_verifier_games(&env, name, 1);
+        }
+        let pair_key = Self::pair_key(&game.player1, &game.player2);
+        env.storage().temporary().set(&pair_key, &session_id);
+        env.storage().temporary().extend_ttl(&pair_key, ttl, ttl);
+        Self::push_active_session(&env, session_id);
+
+        GameStarted {
+            session_id,
+            player1: game.player1.clone(),
+            player2: game.player2.clone(),
+            treasure_hash: game.treasure_hash.clone(),
+        }
+        .publish(&env);
+        if game.time_limit_seconds.is_some() || game.deadline_ledger.is_some() {
+            GameDeadline {
+                session_id,
+                deadline_ledger: Self::effective_deadline(env.clone(), session_id)?,
+            }
+            .publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Submit a ZK proof of treasure discovery.
+    ///
+    /// # Responsibilities
+    /// 1. Validates `public_inputs == game.treasure_hash` (opaque 32-byte
+    ///    comparison — no byte slicing, no field parsing).
+    /// 2. Cross-contract call to the UltraHonk verifier.  If the proof is
+    ///    invalid the verifier traps, reverting the entire transaction.
+    /// 3. Records `energy_used` for the player on success.
+    /// 4. With `DataKey::AutoResolve` on, or the game started with
+    ///    `auto_resolve`, resolves the game as soon as the second player
+    ///    verifies.
+    ///
+    /// # Returns
+    /// `Some(outcome)` if this submission resolved the game, `None` otherwise.
+    ///
+    /// # Debug Mode
+    /// An `Err` return reverts every storage write, so a rejected submission
+    /// leaves no trace.  With `DataKey::DebugMode` on, a client that gets
+    /// `PublicInputMismatch` can store the offending bytes through
+    /// `record_rejected_inputs`.
+    ///
+    /// # Replay Protection
+    /// - The player's authorization is bound to
+    ///   `(session_id, keccak256(proof), public_inputs, energy_used)`, so a
+    ///   captured auth entry authorizes exactly one submission.  Clients that
+    ///   build auth entries by hand must sign these args rather than the raw
+    ///   invocation arguments.
+    /// - `AlreadySubmitted` prevents a player from submitting twice (with
+    ///   `IdempotentSubmit` on, an exact retry is an `Ok(None)` no-op).
+    /// - `PublicInputMismatch` blocks cross-session proof reuse because each
+    ///   session's `treasure_hash` embeds a unique session-bound nullifier.
+    /// - `GameAlreadyResolved` blocks late submissions; a player's late
+    ///   submission may instead be ignored or recorded under
+    ///   `LateSubmitPolicy`.
+    ///
+    /// # Security Note (energy_used)
+    /// `energy_used` is a caller-supplied `u32` in this version.  A dishonest
+    /// player can underreport it.  Future circuit versions should include
+    /// `energy_used` as a verified public output of the Noir circuit.
+    ///
+    /// # Arguments
+    /// * `session_id`    – Session being submitted to.
+    /// * `player`        – Submitting player (must be player1 or player2; a
+    ///                     muxed address matches its underlying account).
+    /// * `proof`         – Raw UltraHonk proof bytes (opaque), at most
+    ///                     `get_max_proof_len` bytes (`ProofTooLarge`).
+    /// * `public_inputs` – Must equal `game.treasure_hash`.
+    /// * `energy_used`   – Energy the player claims to have spent reaching the
+    ///                     treasure (lower = better for the tiebreaker).
+    pub fn submit_zk_proof(
+        env: Env,
+        session_id: u32,
+        player: MuxedAddress,
+        proof: Bytes,
+        public_inputs: Bytes,
+        energy_used: u32,
+    ) -> Result<Option<Outcome>, Error> {
+        // Cheap size checks first, so oversized input never reaches the
+        // hash or the verifier.  A valid `public_inputs` is the 32-byte
+        // treasure hash.
+        if proof.len() > Self::get_max_proof_len(env.clone()) {
+            return Err(Error::ProofTooLarge);
+        }
+        if public_inputs.len() != 32 {
+            return Err(Error::PublicInputMismatch);
+        }
+        let proof_hash: BytesN<32> = env.crypto().keccak256(&proof).into();
+        player.address().require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            proof_hash.into_val(&env),
+            public_inputs.into_val(&env),
+            energy_used.into_val(&env),
+        ]);
+
+        let key = DataKey::Game(session_id);
+        let mut game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        let is_player1 = match Self::check_submission(&env, &game, &player, &public_inputs) {
+            Err(Error::GameAlreadyResolved)
+                if game.player1 == player.address() || game.player2 == player.address() =>
+            {
+                match Self::get_late_submit_policy(env.clone()) {
+                    LateSubmitPolicy::Reject => return Err(Error::GameAlreadyResolved),
+                    LateSubmitPolicy::Ignore => {}
+                    LateSubmitPolicy::Record => {
+                        if game.player1 == player.address() {
+                            game.player1_late_attempts =
+                                game.player1_late_attempts.saturating_add(1);
+                        } else {
+                            game.player2_late_attempts =
+                                game.player2_late_attempts.saturating_add(1);
+                        }
+                        env.storage().temporary().set(&key, &game);
+                    }
+                }
+                return Ok(None);
+            }
+            Err(Error::AlreadySubmitted) if Self::is_idempotent_submit(env.clone()) => {
+                let (energy, hash) = if game.player1 == player.address() {
+                    (game.player1_energy, game.player1_proof_hash)
+                } else {
+                    (game.player2_energy, game.player2_proof_hash)
+                };
+                if energy == Some(energy_used)
+                    && hash == Some(proof_hash)
+                    && public_inputs == Bytes::from(game.treasure_hash)
+                {
+                    return Ok(None);
+                }
+                return Err(Error::AlreadySubmitted);
+            }
+            result => result?,
+        };
+        if game.first_proof_hash.as_ref() == Some(&proof_hash)
+            && Self::is_require_distinct_proofs(env.clone())
+        {
+            return Err(Error::DuplicateProof);
+        }
+        // Soroban has no per-call gas limit, so bound the verifier's work by
+        // its input size instead.
+        if !Self::within_verifier_budget(&env, &proof, &public_inputs) {
+            return Err(Error::VerifierBudgetExceeded);
+        }
+
+        // Cross-contract call: decoupled, stateless UltraHonk verifier.
+        // If the proof is invalid the verifier MUST trap — the whole tx reverts
+        // — unless the game runs in `VerifierMode::Bool`.
+        //
+        // ⚠ Parameter order: the verifier expects (public_inputs, proof_bytes).
+        // Do NOT swap — passing proof as the first argument causes the verifier
+        // to interpret raw proof bytes as Bn254 field elements, which fails
+        // cryptographically and produces Error(Contract, #3 VerificationFailed).
+        let verifier_addr = Self::verifier_for(&env, &game);
+        match game.verifier_mode {
+            VerifierMode::Trap | VerifierMode::Inherit => {
+                UltraHonkVerifierClient::new(&env, &verifier_addr)
+                    .verify_proof(&public_inputs, &proof);
+            }
+            VerifierMode::Bool => {
+                if !BoolVerifierClient::new(&env, &verifier_addr)
+                    .verify_proof(&public_inputs, &proof)
+                {
+                    return Err(Error::InvalidProof);
+                }
+            }
+        }
+        Self::record_verifier_session(&env, &verifier_addr, session_id);
+        Self::record_verifier_stats(&env, &verifier_addr, proof.len());
+
+        // Proof accepted — record player's energy expenditure.
+        let (ledger, timestamp) = (env.ledger().sequence(), env.ledger().timestamp());
+        if is_player1 {
+            game.player1_energy = Some(energy_used);
+            game.player1_proof_hash = Some(proof_hash.clone());
+            game.player1_verified_ledger = Some(ledger);
+            game.player1_verified_at = Some(timestamp);
+        } else {
+            game.player2_energy = Some(energy_used);
+            game.player2_proof_hash = Some(proof_hash.clone());
+            game.player2_verified_ledger = Some(ledger);
+            game.player2_verified_at = Some(timestamp);
+        }
+        game.submissions += 1;
+        if game.first_proof_hash.is_none() {
+            game.first_proof_hash = Some(proof_hash);
+            game.first_verified_ledger = Some(ledger);
+        }
+        if game.player1_energy.is_some() && game.player2_energy.is_some() {
+            Self::update_phase_counts(&env, |c| {
+                c.one_verified = c.one_verified.saturating_sub(1);
+                c.both_verified += 1;
+            });
+        } else {
+            Self::update_phase_counts(&env, |c| {
+                c.open = c.open.saturating_sub(1);
+                c.one_verified += 1;
+            });
+        }
+        let ttl = Self::submission_ttl(&env, &game);
+        game.live_until_ledger = game
+            .live_until_ledger
+            .max(env.ledger().sequence().saturating_add(ttl));
+        env.storage().temporary().set(&key, &game);
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+
+        ProofSubmitted {
+            session_id,
+            energy_used,
+        }
+        .publish(&env);
+        GameSubmitted {
+            session_id,
+            player: player.address(),
+            is_player1,
+        }
+        .publish(&env);
+
+        // Auto-resolve on the second verification, or on the first under
+        // `FirstWins`.  `finalize` marks the game resolved before notifying
+        // the hub, so a re-entrant submission or a later `resolve_game`
+        // cannot settle it twice.
+        let both_verified = game.player1_energy.is_some() && game.player2_energy.is_some();
+        let auto_resolve = game.auto_resolve || Self::is_auto_resolve(env.clone());
+        if game.mode == GameMode::FirstWins || (both_verified && auto_resolve) {
+            return Ok(Some(Self::finalize(&env, session_id, &mut game)));
+        }
+
+        Ok(None)
+    }
+
+    /// Resolve a game whose `deadline_ledger` has passed.
+    ///
+    /// Permissionless, so a silent game never holds points forever: whoever
+    /// submitted wins (see `resolve_game`), and a game nobody proved
+    /// resolves to `NeitherFound`.  The hub is notified as usual.
+    ///
+    /// # Errors
+    /// * `GameFrozen` – the game is resolved and `FrozenResolved` is on.
+    /// * `GameAlreadyResolved` – the game is already resolved.
+    /// * `DeadlineNotReached` – the game has no deadline, or it has not
+    ///   passed yet.
+    pub fn claim_timeout(env: Env, session_id: u32) -> Result<Outcome, Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        if !Self::deadline_passed(&env, &game) {
+            return Err(Error::DeadlineNotReached);
+        }
+        Ok(Self::finalize(&env, session_id, &mut game))
+    }
+
+    /// Concede a live game to the opponent.
+    ///
+    /// The game resolves for the opponent at once if they have verified;
+    /// otherwise the concession is recorded and the next `resolve_game`
+    /// awards them the game without waiting for their proof.
+    ///
+    /// Returns `Some(outcome)` if this call resolved the game.
+    ///
+    /// # Errors
+    /// * `GameAlreadyResolved` – the game is already resolved.
+    /// * `NotPlayer` – `player` is not in this game.
+    /// * `AlreadyForfeited` – a player has already forfeited.
+    pub fn forfeit(env: Env, session_id: u32, player: Address) -> Result<Option<Outcome>, Error> {
+        player.require_auth();
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        let opponent_verified = if player == game.player1 {
+            game.player2_energy.is_some()
+        } else if player == game.player2 {
+            game.player1_energy.is_some()
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        if game.forfeited_by.is_some() {
+            return Err(Error::AlreadyForfeited);
+        }
+
+        game.forfeited_by = Some(player);
+        if opponent_verified {
+            return Ok(Some(Self::finalize(&env, session_id, &mut game)));
+        }
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), &game);
+        Ok(None)
+    }
+
+    /// Keep a session's entry alive for another `get_game_ttl` ledgers
+    /// from now, e.g. through a long-running dispute.  Either player may
+    /// call this, live or resolved; it never shortens the entry's life.
+    ///
+    /// # Errors
+    /// * `NotPlayer` – `player` is not in this game.
+    /// * `GameFrozen` – the game is resolved and `FrozenResolved` is on.
+    pub fn extend_session(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        let mut game = Self::load_game(&env, session_id)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        Self::check_frozen(&env, &game)?;
+        let key = DataKey::Game(session_id);
+        let ttl = Self::get_game_ttl(env.clone());
+        game.live_until_ledger = game
+            .live_until_ledger
+            .max(env.ledger().sequence().saturating_add(ttl));
+        env.storage().temporary().set(&key, &game);
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Claim a resolved game's winnings, settling it on the Game Hub.
+    ///
+    /// Only needed while `ClaimRequired` was on at resolution; the winner
+    /// must authorize.
+    ///
+    /// # Errors
+    /// * `NothingToClaim` – the game is not awaiting a claim.
+    pub fn claim_winnings(env: Env, session_id: u32) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if !game.awaiting_claim {
+            return Err(Error::NothingToClaim);
+        }
+        if game.under_arbitration {
+            return Err(Error::UnderArbitration);
+        }
+        let outcome = Self::game_outcome(&game);
+        let winner = Self::reported_winner(&game, &outcome).ok_or(Error::NothingToClaim)?;
+        winner.require_auth();
+
+        Self::settle_claim(&env, session_id, game, winner);
+        Ok(())
+    }
+
+    /// Settle a game whose winner did not claim within the claim window.
+    ///
+    /// Permissionless, so unclaimed points are never locked in the hub
+    /// forever.  The hub is notified exactly as a claim would.
+    ///
+    /// # Errors
+    /// * `NothingToClaim` – the game is not awaiting a claim.
+    /// * `ClaimWindowOpen` – the winner can still claim.
+    pub fn sweep_unclaimed(env: Env, session_id: u32) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if !game.awaiting_claim {
+            return Err(Error::NothingToClaim);
+        }
+        if game.under_arbitration {
+            return Err(Error::UnderArbitration);
+        }
+        let resolved_ledger = game.resolved_ledger.unwrap_or(0);
+        let window = Self::get_claim_window(env.clone());
+        if env.ledger().sequence() < resolved_ledger.saturating_add(window) {
+            return Err(Error::ClaimWindowOpen);
+        }
+        let outcome = Self::game_outcome(&game);
+        let winner = Self::reported_winner(&game, &outcome).ok_or(Error::NothingToClaim)?;
+
+        Self::settle_claim(&env, session_id, game, winner);
+        Ok(())
+    }
+
+    /// Escalate a resolved game to the arbiter it was started with.
+    ///
+    /// Open to either player for `get_escalation_window` ledgers after
+    /// resolution.  A pending claim stays paused until the arbiter rules.
+    ///
+    /// # Errors
+    /// * `GameNotResolved` – the game is still live.
+    /// * `NotPlayer` – `player` is not in this game.
+    /// * `NoArbiter` – the game has no arbiter.
+    /// * `UnderArbitration` – the game is already escalated.
+    /// * `EscalationClosed` – the window has passed or the arbiter already
+    ///   ruled.
+    pub fn escalate(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        let mut game = Self::load_resolved_game(&env, session_id)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let arbiter = game.arbiter.clone().ok_or(Error::NoArbiter)?;
+        if game.under_arbitration {
+            return Err(Error::UnderArbitration);
+        }
+        let resolved_ledger = game.resolved_ledger.unwrap_or(0);
+        let window = Self::get_escalation_window(env.clone());
+        if game.arbitrated || env.ledger().sequence() >= resolved_ledger.saturating_add(window) {
+            return Err(Error::EscalationClosed);
+        }
+
+        game.under_arbitration = true;
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), &game);
+        GameEscalated {
+            session_id,
+            arbiter,
+            player,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Rule on an escalated game, replacing its outcome.  Arbiter only.
+    ///
+    /// Every outcome query reports the ruling from now on, and a pending
+    /// claim resumes for the ruled winner.  If the ruling leaves no winner
+    /// the hub is notified at once, since nobody could claim.
+    ///
+    /// # Errors
+    /// * `NoArbiter` – the game has no arbiter.
+    /// * `NotUnderArbitration` – the game has not been escalated.
+    pub fn arbitrate(env: Env, session_id: u32, outcome: Outcome) -> Result<(), Error> {
+        let mut game = Self::load_resolved_game(&env, session_id)?;
+        let arbiter = game.arbiter.clone().ok_or(Error::NoArbiter)?;
+        arbiter.require_auth();
+        if !game.under_arbitration {
+            return Err(Error::NotUnderArbitration);
+        }
+
+        game.under_arbitration = false;
+        game.arbitrated = true;
+        game.arbitrated_outcome = outcome.clone();
+        let winner = Self::reported_winner(&game, &outcome);
+        let settle_now = game.awaiting_claim && winner.is_none();
+        if settle_now {
+            game.awaiting_claim = false;
+        }
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), &game);
+        if settle_now {
+            Self::notify_hub(&env, &game, None);
+        }
+        GameArbitrated {
+            session_id,
+            outcome,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Store a signed attestation of a resolved game's outcome.
+    ///
+    /// Contracts cannot hold secret keys, so the holder of the key set by
+    /// `set_attestation_key` signs off-chain and anyone may submit the
+    /// signature.  The signed message is `session_id` (u32 little-endian)
+    /// followed by the outcome code, i.e. the first five bytes of the bridge
+    /// payload.  An invalid signature traps.  Re-attesting replaces the
+    /// stored attestation, e.g. after an arbiter's ruling.
+    ///
+    /// # Errors
+    /// * `GameNotResolved` – the game is still live.
+    /// * `NoAttestationKey` – no key is set.
+    pub fn attest_result(env: Env, session_id: u32, signature: BytesN<64>) -> Result<(), Error> {
+        let game = Self::load_resolved_game(&env, session_id)?;
+        let key: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AttestationKey)
+            .ok_or(Error::NoAttestationKey)?;
+        let outcome = Self::game_outcome(&game);
+        let mut message = Bytes::from_array(&env, &session_id.to_le_bytes());
+        message.push_back(Self::outcome_code(&outcome));
+        env.crypto().ed25519_verify(&key, &message, &signature);
+
+        let storage_key = DataKey::ResultAttestation(session_id);
+        env.storage()
+            .temporary()
+            .set(&storage_key, &(outcome, signature));
+        let ttl = Self::get_game_ttl(env.clone());
+        env.storage().temporary().extend_ttl(&storage_key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Delete a resolved game's state.  Admin only.
+    ///
+    /// With `KeepOutcomeAfterReclaim` on, the outcome stays queryable through
+    /// `get_outcome`; every other query returns `GameNotFound`.
+    ///
+    /// # Errors
+    /// * `GameNotResolved` – the game is still live.
+    /// * `ClaimPending` – the winner has not claimed yet.
+    pub fn reclaim_game(env: Env, session_id: u32) -> Result<(), Error> {
+        Self::require_admin(&env);
+        let game = Self::load_resolved_game(&env, session_id)?;
+        if game.awaiting_claim {
+            return Err(Error::ClaimPending);
+        }
+        if game.under_arbitration {
+            return Err(Error::UnderArbitration);
+        }
+        Self::delete_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    /// Delete a resolved game's state to reclaim its storage, publishing
+    /// `GameClosed` with the final outcome first.  Anyone can call this.
+    ///
+    /// Like `reclaim_game` in every other respect.  The players' session
+    /// indexes keep the id as history.  The game is kept until the session
+    /// reuse cooldown has passed, since `start_game` can only enforce the
+    /// cooldown while the resolved game exists.
+    ///
+    /// # Errors
+    /// * `GameNotResolved` – the game is still live.
+    /// * `ClaimPending` – the winner has not claimed yet.
+    /// * `UnderArbitration` – the arbiter has not ruled yet.
+    /// * `EscalationOpen` – a player can still escalate the game.
+    /// * `SessionReuseTooSoon` – the session reuse cooldown has not passed.
+    pub fn close_session(env: Env, session_id: u32) -> Result<(), Error> {
+        let game = Self::load_resolved_game(&env, session_id)?;
+        if game.awaiting_claim {
+            return Err(Error::ClaimPending);
+        }
+        if game.under_arbitration {
+            return Err(Error::UnderArbitration);
+        }
+        if Self::escalation_open(&env, &game) {
+            return Err(Error::EscalationOpen);
+        }
+        let resolved_ledger = game.resolved_ledger.unwrap_or(0);
+        let cooldown_end =
+            resolved_ledger.saturating_add(Self::get_session_reuse_cooldown(env.clone()));
+        if env.ledger().sequence() < cooldown_end {
+            return Err(Error::SessionReuseTooSoon);
+        }
+
+        GameClosed {
+            session_id,
+            outcome: Self::game_outcome(&game),
+        }
+        .publish(&env);
+        Self::delete_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    /// Cancel a live game neither player has verified, deleting its state
+    /// and asking the hub to unlock both players' points via `cancel_game`.
+    /// An optional `reason` is kept for `get_cancel_reason`.
+    ///
+    /// Both players must authorize, as for `start_game`.
+    ///
+    /// # Errors
+    /// * `GameFrozen` – the game is resolved and `FrozenResolved` is on.
+    /// * `GameAlreadyResolved` – the game is already resolved.
+    /// * `AlreadySubmitted` – a player has verified; resolve it instead.
+    pub fn cancel_game(env: Env, session_id: u32, reason: Option<Symbol>) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        game.player1.require_auth();
+        game.player2.require_auth();
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        if game.player1_energy.is_some() || game.player2_energy.is_some() {
+            return Err(Error::AlreadySubmitted);
+        }
+
+        env.storage().temporary().remove(&DataKey::Game(session_id));
+        env.storage()
+            .temporary()
+            .remove(&DataKey::GameMaxPoints(session_id));
+        Self::release_game_bindings(&env, session_id, &game);
+        Self::drop_live_game(&env, session_id, &game);
+        Self::update_phase_counts(&env, |c| c.open = c.open.saturating_sub(1));
+
+        if let Some(hub) = &game.hub {
+            GameHubClient::new(&env, hub).cancel_game(&game.hub_session_id);
+        }
+        Self::record_cancel_reason(&env, session_id, &reason);
+        GameCancelled {
+            session_id,
+            hub_session_id: game.hub_session_id,
+            reason,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Upload one chunk of a proof too large for a single transaction.
+    ///
+    /// Chunks must arrive in order starting at `chunk_index` 0, each with the
+    /// same `total_chunks`; sending chunk 0 again restarts the upload.  Once
+    /// all chunks are in, call `finalize_proof`.
+    ///
+    /// # Errors
+    /// * `NotPlayer` – `player` is not in this session.
+    /// * `GameAlreadyResolved` – the session no longer accepts proofs.
+    /// * `IncompleteProof` – the chunk is out of order or `total_chunks`
+    ///   changed mid-upload.
+    pub fn submit_proof_chunk(
+        env: Env,
+        session_id: u32,
+        player: MuxedAddress,
+        chunk_index: u32,
+        chunk: Bytes,
+        total_chunks: u32,
+    ) -> Result<(), Error> {
+        let account = player.address();
+        account.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        let game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        if account != game.player1 && account != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        let key = DataKey::ProofChunks(session_id, account);
+        let mut upload = if chunk_index == 0 {
+            ProofUpload {
+                total_chunks,
+                received: 0,
+                proof: Bytes::new(&env),
+            }
+        } else {
+            env.storage()
+                .temporary()
+                .get(&key)
+                .ok_or(Error::IncompleteProof)?
+        };
+        if chunk_index != upload.received
+            || chunk_index >= total_chunks
+            || total_chunks != upload.total_chunks
+        {
+            return Err(Error::IncompleteProof);
+        }
+
+        upload.proof.append(&chunk);
+        upload.received += 1;
+        env.storage().temporary().set(&key, &upload);
+        let ttl = Self::get_game_ttl(env.clone());
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Reassemble a chunked proof and submit it exactly like
+    /// `submit_zk_proof`.
+    ///
+    /// The player's authorization is bound to the same arguments as
+    /// `submit_zk_proof`, with `keccak256` taken over the reassembled proof.
+    ///
+    /// # Errors
+    /// * `IncompleteProof` – no upload exists or chunks are still missing.
+    /// * Any error `submit_zk_proof` can return.
+    pub fn finalize_proof(
+        env: Env,
+        session_id: u32,
+        player: MuxedAddress,
+        public_inputs: Bytes,
+        energy_used: u32,
+    ) -> Result<Option<Outcome>, Error> {
+        let key = DataKey::ProofChunks(session_id, player.address());
+        let upload: ProofUpload = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::IncompleteProof)?;
+        if upload.received != upload.total_chunks {
+            return Err(Error::IncompleteProof);
+        }
+
+        env.storage().temporary().remove(&key);
+        Self::submit_zk_proof(
+            env,
+            session_id,
+            player,
+            upload.proof,
+            public_inputs,
+            energy_used,
+        )
+    }
+
+    /// Resolve the game and report the outcome to the Game Hub.
+    ///
+    /// Can be called by anyone (permissionless) unless `PlayersOnlyResolve`
+    /// is on, in which case only `resolve_game_as` by a player resolves and
+    /// this returns `ResolutionGated`.  Idempotent after first call, unless
+    /// `FrozenResolved` makes repeat calls fail with `GameFrozen`.
+    /// Requires at least one player to have submitted a proof; while only
+    /// one has, the `SecondPlayerWindow` must also have closed.
+    ///
+    /// ## Winner Resolution
+    ///
+    /// | p1_energy     | p2_energy     | Outcome            | GameHub outcome    |
+    /// |---------------|---------------|--------------------|--------------------|
+    /// | Some(e1)      | None          | Player1Won         | 0 (player 1)       |
+    /// | None          | Some(e2)      | Player2Won         | 1 (player 2)       |
+    /// | Some(e1)      | Some(e2), e1 < e2 | Player1Won    | 0 (player 1)       |
+    /// | Some(e1)      | Some(e2), e2 < e1 | Player2Won    | 1 (player 2)       |
+    /// | Some(e1)      | Some(e2), e1 == e2 | BothFoundTreasure | 2 (draw)          |
+    /// | None          | None          | Error: NeitherPlayerSubmitted | – |
+    ///
+    /// # Arguments
+    /// * `session_id` – The session to resolve.
+    pub fn resolve_game(env: Env, session_id: u32) -> Result<Outcome, Error> {
+        Self::resolve(&env, session_id, false)
+    }
+
+    /// Describe the authorizations resolving `session_id` as `caller`
+    /// requires under the current configuration.
+    ///
+    /// Empty while resolution is permissionless.  Under `PlayersOnlyResolve`
+    /// a player must call `resolve_game_as`, authorizing
+    /// `(session_id, caller)`.
+    ///
+    /// # Errors
+    /// * `GameNotFound` – no game exists for `session_id`.
+    /// * `ResolutionGated` – `caller` cannot resolve this game at all.
+    pub fn resolve_auth_preview(
+        env: Env,
+        session_id: u32,
+        caller: Address,
+    ) -> Result<Vec<AuthRequirement>, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if !Self::is_players_only_resolve(env.clone()) {
+            return Ok(Vec::new(&env));
+        }
+        if caller != game.player1 && caller != game.player2 {
+            return Err(Error::ResolutionGated);
+        }
+        Ok(vec![
+            &env,
+            AuthRequirement {
+                address: caller.clone(),
+                args: vec![&env, session_id.into_val(&env), caller.into_val(&env)],
+            },
+        ])
+    }
+
+    /// Preview the `end_game_with_outcome` code `resolve_game` would report
+    /// to the hub now, without changing state or calling the hub.
+    ///
+    /// A tie always reports 2 (draw).  Under
+    /// `ClaimRequired` the same code is sent at claim time.
+    ///
+    /// # Errors
+    /// * `GameAlreadyResolved` – the game has already been reported.
+    /// * `NeitherPlayerSubmitted` – `resolve_game` would fail.
+    pub fn preview_hub_report(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        if game.player1_energy.is_none() && game.player2_energy.is_none() {
+            return Err(Error::NeitherPlayerSubmitted);
+        }
+        let outcome = Self::game_outcome(&game);
+        let winner = Self::reported_winner(&game, &outcome);
+        Ok(Self::hub_outcome(&game, &winner))
+    }
+
+    /// Resolve the game as `resolver` and credit the resolution reward.
+    ///
+    /// Settles exactly like `resolve_game`.  The resolver is credited
+    /// `get_resolution_reward` points (see `get_reward_balance`) only when
+    /// this call performs the resolution, and — with `PlayersOnlyReward` on —
+    /// only when `resolver` is player1 or player2.  Anyone else still settles
+    /// the game but earns nothing.
+    ///
+    /// # Arguments
+    /// * `session_id` – The session to resolve.
+    /// * `resolver`   – Account claiming the reward; must authorize.
+    pub fn resolve_game_as(env: Env, session_id: u32, resolver: Address) -> Result<Outcome, Error> {
+        resolver.require_auth();
+
+        let game = Self::load_game(&env, session_id)?;
+        let already_resolved = game.resolved;
+        let is_player = resolver == game.player1 || resolver == game.player2;
+        let outcome = Self::resolve(&env, session_id, is_player)?;
+        if already_resolved {
+            return Ok(outcome);
+        }
+
+        let reward = Self::get_resolution_reward(env.clone());
+        if reward > 0 && (is_player || !Self::is_players_only_reward(env.clone())) {
+            let key = DataKey::RewardBalance(resolver);
+            let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(balance + reward));
+        }
+
+        Ok(outcome)
+    }
+
+    /// Resolve `session_id` exactly like `resolve_game`, then start a
+    /// rematch between the same players with the same stakes and category
+    /// under `new_session_id`.
+    ///
+    /// The rematch takes its treasure hash from `preregister_target`, and
+    /// each player must authorize it as for `start_game`.  A rematch that
+    /// cannot start — no pre-registered target, or any error `start_game`
+    /// would return — leaves the resolution in place.  A missing player
+    /// authorization still aborts the whole call.
+    ///
+    /// Returns the outcome and whether the rematch started.
+    pub fn resolve_and_rematch(
+        env: Env,
+        session_id: u32,
+        new_session_id: u32,
+    ) -> Result<(Outcome, bool), Error> {
+        let outcome = Self::resolve(&env, session_id, false)?;
+        let game = Self::load_game(&env, session_id)?;
+
+        let target_key = DataKey::PreregisteredTarget(new_session_id);
+        let Some(target) = env.storage().temporary().get(&target_key) else {
+            return Ok((outcome, false));
+        };
+        let options = GameOptions {
+            category: game.category,
+            currency_decimals: game.currency_decimals,
+            currency_symbol: game.currency_symbol,
+            ..Default::default()
+        };
+        let started = Self::start_session(
+            &env,
+            new_session_id,
+            game.player1,
+            game.player2,
+            game.player1_points,
+            game.player2_points,
+            target,
+            options,
+            true,
+        )
+        .is_ok();
+        Ok((outcome, started))
+    }
+
+    /// Start a rematch of the resolved game `old_session_id` under
+    /// `new_session_id`, with the same players, category and currency.
+    ///
+    /// Otherwise identical to `start_game`: both players authorize their new
+    /// commitments, and the nullifier is derived afresh for `new_session_id`,
+    /// so the frontend builds `treasure_hash` for the new session (or the
+    /// game server pre-registers it).
+    ///
+    /// # Errors
+    /// * `GameNotFound` – no game under `old_session_id`.
+    /// * `GameNotResolved` – the old game is still live.
+    /// * `SessionExists` – a game, live or resolved, holds `new_session_id`.
+    /// * Any error `start_game` would return.
+    pub fn rematch(
+        env: Env,
+        old_session_id: u32,
+        new_session_id: u32,
+        player1_points: i128,
+        player2_points: i128,
+        treasure_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let game = Self::load_game(&env, old_session_id)?;
+        if !game.resolved {
+            return Err(Error::GameNotResolved);
+        }
+        if Self::load_game(&env, new_session_id).is_ok() {
+            return Err(Error::SessionExists);
+        }
+        let options = GameOptions {
+            category: game.category,
+            currency_decimals: game.currency_decimals,
+            currency_symbol: game.currency_symbol,
+            ..Default::default()
+        };
+        Self::start_session(
+            &env,
+            new_session_id,
+            game.player1,
+            game.player2,
+            player1_points,
+            player2_points,
+            treasure_hash,
+            options,
+            true,
+        )
+    }
+
+    /// Resolve up to `MAX_RESOLVE_BATCH` sessions in one call, each exactly
+    /// like `resolve_game`, returning their results in input order.
+    ///
+    /// A session that cannot be resolved yields its error without aborting
+    /// the rest; `resolve` checks everything before writing, so a failed
+    /// entry leaves no partial state.  The Game Hub is called through
+    /// `try_` calls before any write, so a hub that traps yields
+    /// `HubCallFailed` for its session only.
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` – more than `MAX_RESOLVE_BATCH` session ids.
+    pub fn resolve_many(
+        env: Env,
+        session_ids: Vec<u32>,
+    ) -> Result<Vec<Result<Outcome, Error>>, Error> {
+        if session_ids.len() > MAX_RESOLVE_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        let mut results = Vec::new(&env);
+        for session_id in session_ids.iter() {
+            results.push_back(Self::resolve_isolated(&env, session_id));
+        }
+        Ok(results)
+    }
+
+    // ========================================================================
+    // Queries
+    // ========================================================================
+
+    /// Like `would_accept_submission`, but return every failing check at
+    /// once (empty if the submission would reach the verifier), including
+    /// `DuplicateProof` under `RequireDistinctProofs`.
+    ///
+    /// Returns just `GameNotFound` for an unknown session.
+    pub fn submission_diagnostics(
+        env: Env,
+        session_id: u32,
+        player: MuxedAddress,
+        proof: Bytes,
+        public_inputs: Bytes,
+    ) -> Vec<Error> {
+        let Ok(game) = Self::load_game(&env, session_id) else {
+            return vec![&env, Error::GameNotFound];
+        };
+        let mut failures = Self::submission_failures(&env, &game, &player, &public_inputs);
+        let proof_hash: BytesN<32> = env.crypto().keccak256(&proof).into();
+        if game.first_proof_hash == Some(proof_hash)
+            && Self::is_require_distinct_proofs(env.clone())
+        {
+            failures.push_back(Error::DuplicateProof);
+        }
+        if !Self::within_verifier_budget(&env, &proof, &public_inputs) {
+            failures.push_back(Error::VerifierBudgetExceeded);
+        }
+        if proof.len() > Self::get_max_proof_len(env.clone()) {
+            failures.push_back(Error::ProofTooLarge);
+        }
+        failures
+    }
+
+    /// Preflight a submission: run every check `submit_zk_proof` performs
+    /// before calling the verifier, without calling it or mutating state.
+    ///
+    /// Returns `Ok(())` if the submission would reach the verifier, or the
+    /// first blocking `Error` otherwise.
+    pub fn would_accept_submission(
+        env: Env,
+        session_id: u32,
+        player: MuxedAddress,
+        public_inputs: Bytes,
+    ) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Self::check_submission(&env, &game, &player, &public_inputs)?;
+        Ok(())
+    }
+
+    /// Check `public_inputs` against the session's target without calling
+    /// the verifier, so a UI can catch the most common mistake before
+    /// paying for a submission.
+    ///
+    /// # Errors
+    /// * `GameNotFound` – the session does not exist.
+    /// * `PublicInputMismatch` – `public_inputs` is not the session's
+    ///   32-byte `treasure_hash`.
+    pub fn check_public_inputs(
+        env: Env,
+        session_id: u32,
+        public_inputs: Bytes,
+    ) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if public_inputs != Bytes::from(game.treasure_hash) {
+            return Err(Error::PublicInputMismatch);
+        }
+        Ok(())
+    }
+
+    /// Preflight a resolution by `caller`: run every check `resolve_game_as`
+    /// performs, without mutating state or calling the hub.
+    ///
+    /// Returns the outcome the call would report (the stored one for a
+    /// resolved game), or the first blocking `Error` otherwise.
+    pub fn resolve_diagnostics(
+        env: Env,
+        session_id: u32,
+        caller: Address,
+    ) -> Result<Outcome, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        if !game.resolved {
+            let is_player = caller == game.player1 || caller == game.player2;
+            Self::check_resolution(&env, &game, is_player)?;
+        }
+        Ok(Self::game_outcome(&game))
+    }
+
+    /// Retrieve full game state for a session.
+    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
+        Self::load_game(&env, session_id)
+    }
+
+    /// Return `keccak256` of the session's `Game` in XDR; any change to the
+    /// stored game changes the hash.
+    pub fn game_state_hash(env: Env, session_id: u32) -> Result<BytesN<32>, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(env.crypto().keccak256(&game.to_xdr(&env)).into())
+    }
+
+    /// Bundle the session's state hash, outcome and the current ledger
+    /// into a `StateProof` a light client can cache and later compare
+    /// against `game_state_hash`.
+    pub fn state_proof(env: Env, session_id: u32) -> Result<StateProof, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let state_hash: BytesN<32> = env.crypto().keccak256(&game.clone().to_xdr(&env)).into();
+        let outcome = if game.resolved {
+            Self::game_outcome(&game)
+        } else {
+            Outcome::NeitherFound
+        };
+        let ledger = env.ledger().sequence();
+
+        let mut preimage = Bytes::from_array(&env, &session_id.to_le_bytes());
+        preimage.append(&state_hash.clone().into());
+        preimage.push_back(game.resolved as u8);
+        preimage.push_back(Self::outcome_code(&outcome));
+        preimage.extend_from_array(&ledger.to_le_bytes());
+        Ok(StateProof {
+            session_id,
+            state_hash,
+            resolved: game.resolved,
+            outcome,
+            ledger,
+            commitment: env.crypto().keccak256(&preimage).into(),
+        })
+    }
+
+    /// Look up the session id bound to `name` by `start_game_named`.
+    ///
+    /// # Errors
+    /// * `GameNotFound` – no live or resolved game holds `name`.
+    pub fn resolve_session_name(env: Env, name: Bytes) -> Result<u32, Error> {
+        Self::named_session(&env, &name).ok_or(Error::GameNotFound)
+    }
+
+    /// Return the sessions `player` has taken part in, oldest first.
+    ///
+    /// The index holds at most `get_max_player_sessions` entries; see
+    /// `record_player_session` for how entries are evicted.
+    pub fn get_player_sessions(env: Env, player: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerSessions(player))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Return every stored session in `player`'s index with its current
+    /// phase, oldest first, so a dashboard renders in one call.
+    ///
+    /// Bounded by `get_max_player_sessions`; sessions whose game has expired
+    /// or been reclaimed are skipped.
+    pub fn player_dashboard(env: Env, player: Address) -> Vec<(u32, GamePhase)> {
+        let mut dashboard = Vec::new(&env);
+        for session_id in Self::get_player_sessions(env.clone(), player).iter() {
+            if let Some(game) = env
+                .storage()
+                .temporary()
+                .get::<_, Game>(&DataKey::Game(session_id))
+            {
+                dashboard.push_back((session_id, Self::game_phase(&game)));
+            }
+        }
+        dashboard
+    }
+
+    /// Return the current length of `player`'s session index.
+    pub fn player_session_count(env: Env, player: Address) -> u32 {
+        Self::get_player_sessions(env, player).len()
+    }
+
+    /// Record `public_inputs` that `submit_zk_proof` rejected with
+    /// `PublicInputMismatch`, for `get_last_rejected_inputs`.
+    ///
+    /// `player` must authorize.  Does nothing while debug mode is off, or if
+    /// the bytes actually match the session's target.  The bytes are
+    /// client-reported: nothing ties them to a submission that actually
+    /// failed, so treat them as a debugging hint only.
+    ///
+    /// # Errors
+    /// * `NotPlayer` – `player` is not in this session.
+    /// * `InputsTooLarge` – `public_inputs` is longer than
+    ///   `MAX_REJECTED_INPUTS_LEN` bytes.
+    /// * `GameFrozen` – the game is resolved and `FrozenResolved` is on.
+    /// * `GameAlreadyResolved` – the game no longer accepts submissions.
+    pub fn record_rejected_inputs(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        public_inputs: Bytes,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        let mut game = Self::load_game(&env, session_id)?;
+        let is_player1 = player == game.player1;
+        if !is_player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if public_inputs.len() > MAX_REJECTED_INPUTS_LEN {
+            return Err(Error::InputsTooLarge);
+        }
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        if !Self::is_debug_mode(env.clone())
+            || public_inputs == Bytes::from(game.treasure_hash.clone())
+        {
+            return Ok(());
+        }
+        if is_player1 {
+            game.player1_rejected_inputs = Some(public_inputs);
+        } else {
+            game.player2_rejected_inputs = Some(public_inputs);
+        }
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), &game);
+        Ok(())
+    }
+
+    /// Return the `public_inputs` of `player`'s last mismatched submission.
+    ///
+    /// Only populated through `record_rejected_inputs` while debug mode is
+    /// on; diff it against `get_treasure_hash` to find the client-side
+    /// encoding error.
+    ///
+    /// # Errors
+    /// * `NotPlayer` – `player` is not in this session.
+    pub fn get_last_rejected_inputs(
+        env: Env,
+        session_id: u32,
+        player: Address,
+    ) -> Result<Option<Bytes>, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if player == game.player1 {
+            Ok(game.player1_rejected_inputs)
+        } else if player == game.player2 {
+            Ok(game.player2_rejected_inputs)
+        } else {
+            Err(Error::NotPlayer)
+        }
+    }
+
+    /// Return the resolution rewards credited to `resolver`.
+    pub fn get_reward_balance(env: Env, resolver: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RewardBalance(resolver))
+            .unwrap_or(0)
+    }
+
+    /// Return the treasure hash (public input) for a session.
+    ///
+    /// Frontends should use this as the `xy_nullifier_hashed` circuit input.
+    pub fn get_treasure_hash(env: Env, session_id: u32) -> Result<BytesN<32>, Error> {
+        Ok(Self::load_game(&env, session_id)?.treasure_hash)
+    }
+
+    /// Return the arguments `submit_zk_proof` would pass to the verifier for
+    /// `proof` in this session, in the verifier's parameter order:
+    /// `(public_inputs, proof_bytes)`.
+    ///
+    /// `public_inputs` is the only value `submit_zk_proof` accepts, the
+    /// session's `treasure_hash`.  Use this to replay a verifier call by hand.
+    pub fn get_verifier_call_args(
+        env: Env,
+        session_id: u32,
+        proof: Bytes,
+    ) -> Result<(Bytes, Bytes), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok((game.treasure_hash.into(), proof))
+    }
+
+    /// Return the verifier contract that checks `session_id`'s proofs.
+    pub fn get_session_verifier(env: Env, session_id: u32) -> Result<Address, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(Self::verifier_for(&env, &game))
+    }
+
+    /// Return the outcome of a resolved game.
+    ///
+    /// Returns `GameNotResolved` while the game is still live.  Reclaimed
+    /// games answer only if `KeepOutcomeAfterReclaim` was on at reclaim.
+    pub fn get_outcome(env: Env, session_id: u32) -> Result<Outcome, Error> {
+        let game = match Self::load_resolved_game(&env, session_id) {
+            Err(Error::GameNotFound) => {
+                return env
+                    .storage()
+                    .temporary()
+                    .get(&DataKey::ReclaimedOutcome(session_id))
+                    .ok_or(Error::GameNotFound)
+            }
+            result => result?,
+        };
+        Ok(Self::game_outcome(&game))
+    }
+
+    /// Return the outcome the session stands at now, live or resolved,
+    /// without writing storage or calling the hub.
+    ///
+    /// Unlike `resolve_game`, a game nobody has proved yet reports
+    /// `NeitherFound` instead of failing with `NeitherPlayerSubmitted`.
+    pub fn peek_outcome(env: Env, session_id: u32) -> Result<Outcome, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(Self::game_outcome(&game))
+    }
+
+    /// Whether `proof_hash` (`keccak256` of the proof bytes) is the session's
+    /// recorded proof, i.e. whether `RequireDistinctProofs` would reject a
+    /// submission of it as a replay.
+    pub fn is_proof_used(env: Env, session_id: u32, proof_hash: BytesN<32>) -> Result<bool, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(game.first_proof_hash == Some(proof_hash))
+    }
+
+    /// Return the attested outcome of a session and the ed25519 signature
+    /// over it (see `attest_result`).
+    ///
+    /// # Errors
+    /// * `NoAttestation` – no attestation was stored for `session_id`.
+    pub fn get_result_attestation(
+        env: Env,
+        session_id: u32,
+    ) -> Result<(Outcome, BytesN<64>), Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ResultAttestation(session_id))
+            .ok_or(Error::NoAttestation)
+    }
+
+    /// Return the `(ledger sequence, timestamp)` at which a game was resolved.
+    ///
+    /// Returns `GameNotResolved` while the game is still live.
+    pub fn get_resolution_time(env: Env, session_id: u32) -> Result<(u32, u64), Error> {
+        let game = Self::load_resolved_game(&env, session_id)?;
+        match (game.resolved_ledger, game.resolved_at) {
+            (Some(ledger), Some(timestamp)) => Ok((ledger, timestamp)),
+            _ => Err(Error::GameNotResolved),
+        }
+    }
+
+    /// Return the player reported to the Game Hub as the winner of a resolved
+    /// game, or `None` on a draw or when neither player won.
+    ///
+    /// Returns `GameNotResolved` while the game is still live.
+    pub fn get_winner(env: Env, session_id: u32) -> Result<Option<Address>, Error> {
+        let game = Self::load_resolved_game(&env, session_id)?;
+        let outcome = Self::game_outcome(&game);
+        Ok(Self::reported_winner(&game, &outcome))
+    }
+
+    /// Return how many more ledgers the session's entry is guaranteed to
+    /// live, so frontends can warn players before it expires (see
+    /// `extend_session`).  Returns `GameNotFound` once it has expired.
+    pub fn get_session_ttl(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(game
+            .live_until_ledger
+            .saturating_sub(env.ledger().sequence()))
+    }
+
+    /// Return the first ledger at which the session stops accepting proofs.
+    ///
+    /// Reconciles every closing condition: resolution, both players (or the
+    /// submission budget) being spent, the `time_limit_seconds` window, the
+    /// `deadline_ledger` and expiry of the game entry.  Already-closed sessions return the ledger
+    /// they closed at when known (resolution), else the current ledger.  The
+    /// time limit is wall-clock based, so its ledger is an estimate assuming
+    /// `SECONDS_PER_LEDGER`-second ledgers.
+    pub fn effective_deadline(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let now = env.ledger().sequence();
+        if let Some(resolved_ledger) = game.resolved_ledger {
+            return Ok(resolved_ledger);
+        }
+        let both_submitted = game.player1_energy.is_some() && game.player2_energy.is_some();
+        if both_submitted || game.submissions >= game.max_submissions {
+            return Ok(now);
+        }
+
+        let mut deadline = game.live_until_ledger.saturating_add(1);
+        if let Some(limit) = game.time_limit_seconds {
+            let closes_at = game.started_at.saturating_add(limit);
+            let remaining = closes_at.saturating_sub(env.ledger().timestamp());
+            let ledgers = if env.ledger().timestamp() > closes_at {
+                0
+            } else {
+                remaining / SECONDS_PER_LEDGER + 1
+            };
+            let estimate = now.saturating_add(u32::try_from(ledgers).unwrap_or(u32::MAX));
+            deadline = deadline.min(estimate);
+        }
+        if let Some(last) = game.deadline_ledger {
+            deadline = deadline.min(last.saturating_add(1));
+        }
+        Ok(deadline)
+    }
+
+    /// Whether `player` can still end up as the reported winner.
+    ///
+    /// Resolved games answer whether `player` won.  Live games assume the
+    /// best case for `player`: if they have not submitted yet, a submission
+    /// with zero energy, provided submissions are still open.  So a player
+    /// who has not submitted can still win against any opponent except one
+    /// who spent zero energy, since an equal-energy tie is a draw.
+    ///
+    /// # Errors
+    /// * `NotPlayer` – `player` is not in this session.
+    pub fn can_still_win(env: Env, session_id: u32, player: Address) -> Result<bool, Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        let is_player1 = player == game.player1;
+        if !is_player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        if !game.resolved {
+            let time_up = game.time_limit_seconds.is_some_and(|limit| {
+                env.ledger().timestamp() > game.started_at.saturating_add(limit)
+            });
+            let closed = time_up
+                || Self::deadline_passed(&env, &game)
+                || game.submissions >= game.max_submissions;
+            let own = if is_player1 {
+                &mut game.player1_energy
+            } else {
+                &mut game.player2_energy
+            };
+            if own.is_none() {
+                if closed {
+                    return Ok(false);
+                }
+                *own = Some(0);
+            }
+        }
+        let outcome = Self::game_outcome(&game);
+        Ok(Self::reported_winner(&game, &outcome) == Some(player))
+    }
+
+    /// Return the nullifier derived for a session at `start_game`.
+    pub fn get_nullifier(env: Env, session_id: u32) -> Result<BytesN<32>, Error> {
+        Ok(Self::load_game(&env, session_id)?.nullifier)
+    }
+
+    /// Return every input of a session's nullifier derivation, plus the exact
+    /// preimage, so clients can reproduce `get_nullifier` byte for byte.
+    pub fn get_derivation_params(env: Env, session_id: u32) -> Result<DerivationParams, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let domain_tag = if game.derivation_version == DERIVATION_V1 {
+            Bytes::from_slice(&env, DOMAIN_TAG)
+        } else {
+            Bytes::new(&env)
+        };
+        let preimage = Self::nullifier_preimage(
+            &env,
+            game.derivation_version,
+            session_id,
+            &game.player1,
+            &game.player2,
+            game.deadline_ledger,
+        );
+        Ok(DerivationParams {
+            version: game.derivation_version,
+            hash: Symbol::new(&env, "keccak256"),
+            domain_tag,
+            session_id,
+            player1: game.player1,
+            player2: game.player2,
+            contract: env.current_contract_address(),
+            deadline_ledger: game.deadline_ledger,
+            salt: Bytes::new(&env),
+            preimage,
+        })
+    }
+
+    /// Hash an arbitrary derivation variant, independent of the contract's
+    /// current settings.
+    ///
+    /// The preimage is rebuilt from `params` as `domain_tag ‖ session_id_be ‖
+    /// player1 strkey ‖ player2 strkey ‖ contract strkey [‖ deadline_ledger_be]
+    /// ‖ salt`; the `domain_tag` is used
+    /// verbatim, so `version` is informational and the supplied `preimage`
+    /// is ignored.  Passing `get_derivation_params` output back reproduces
+    /// the session's nullifier.
+    ///
+    /// # Errors
+    /// * `UnsupportedHash` – `hash` is neither `keccak256` nor `sha256`.
+    pub fn compute_target_variant(env: Env, params: DerivationParams) -> Result<BytesN<32>, Error> {
+        let mut preimage = params.domain_tag;
+        preimage.extend_from_array(&params.session_id.to_be_bytes());
+        preimage.append(&params.player1.to_string().to_bytes());
+        preimage.append(&params.player2.to_string().to_bytes());
+        preimage.append(&params.contract.to_string().to_bytes());
+        if let Some(deadline) = params.deadline_ledger {
+            preimage.extend_from_array(&deadline.to_be_bytes());
+        }
+        preimage.append(&params.salt);
+
+        if params.hash == Symbol::new(&env, "keccak256") {
+            Ok(env.crypto().keccak256(&preimage).into())
+        } else if params.hash == Symbol::new(&env, "sha256") {
+            Ok(env.crypto().sha256(&preimage).into())
+        } else {
+            Err(Error::UnsupportedHash)
+        }
+    }
+
+    /// Derive the nullifier a session started now would receive, with the
+    /// `deadline_ledger` it will be started with, if any.
+    ///
+    /// Frontends call this before `start_game` to build the `treasure_hash`.
+    pub fn derive_nullifier(
+        env: Env,
+        session_id: u32,
+        player1: MuxedAddress,
+        player2: MuxedAddress,
+        deadline_ledger: Option<u32>,
+    ) -> BytesN<32> {
+        let (player1, player2) = (player1.address(), player2.address());
+        let version = if Self::is_domain_separation_enabled(env.clone()) {
+            DERIVATION_V1
+        } else {
+            DERIVATION_V0
+        };
+        Self::compute_nullifier(
+            &env,
+            version,
+            session_id,
+            &player1,
+            &player2,
+            deadline_ledger,
+        )
+    }
+
+    /// Return the sessions in which `verifier` accepted a proof, oldest first.
+    ///
+    /// Only the most recent `MAX_VERIFIER_SESSIONS` sessions are kept; use
+    /// this to gauge a verifier's usage before deprecating it.
+    pub fn games_verified_by(env: Env, verifier: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerifierSessions(verifier))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Return proof-length aggregates over every proof `verifier` accepted.
+    pub fn verifier_stats(env: Env, verifier: Address) -> VerifierStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerifierStats(verifier))
+            .unwrap_or_default()
+    }
+
+    /// Return the domain-separation tag used by `DERIVATION_V1`.
+    pub fn get_domain_tag(env: Env) -> Bytes {
+        Bytes::from_slice(&env, DOMAIN_TAG)
+    }
+
+    /// Return how many more `submit_zk_proof` calls the session will accept
+    /// before its submission budget is exhausted.
+    ///
+    /// Clients use this to warn players before the cap is reached.
+    pub fn remaining_submissions(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        Ok(game.max_submissions.saturating_sub(game.submissions))
+    }
+
+    /// Whether the contract is fully configured and ready to host games.
+    ///
+    /// True only when the contract is not paused, admin and verifier are
+    /// set, the verifier is a deployed Wasm contract, and admin, verifier,
+    /// any default hub and this contract are all distinct.
+    pub fn is_operational(env: Env) -> bool {
+        if Self::is_paused(env.clone()) {
+            return false;
+        }
+        let storage = env.storage().instance();
+        let Some(verifier) = storage.get::<_, Address>(&DataKey::VerifierAddress) else {
+            return false;
+        };
+        let Some(admin) = storage.get::<_, Address>(&DataKey::Admin) else {
+            return false;
+        };
+
+        let this = env.current_contract_address();
+        if verifier == this || admin == verifier || !Self::is_wasm_contract(&verifier) {
+            return false;
+        }
+        match storage.get::<_, Address>(&DataKey::GameHubAddress) {
+            Some(hub) => hub != verifier && hub != this && hub != admin,
+            None => true,
+        }
+    }
+
+    /// Return the player's last `n` resolved sessions with whether they won,
+    /// oldest first.
+    ///
+    /// `n` is capped at `MAX_PLAYER_RESULTS`, the number of results kept per
+    /// player.  Draws and games nobody proved count as losses.
+    pub fn player_results(env: Env, player: Address, n: u32) -> Vec<(u32, bool)> {
+        let results: Vec<(u32, bool)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerResults(player))
+            .unwrap_or(Vec::new(&env));
+        let n = n.min(MAX_PLAYER_RESULTS).min(results.len());
+        results.slice(results.len() - n..)
+    }
+
+    /// Return the player's cumulative wins, losses and draws; zero for a
+    /// player with no resolved games.
+    pub fn get_stats(env: Env, player: Address) -> PlayerStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stats(player))
+            .unwrap_or_default()
+    }
+
+    /// Count games resolved since `from_ledger` by outcome.
+    ///
+    /// Resolutions are counted in buckets of `OUTCOME_BUCKET_LEDGERS`
+    /// ledgers, so `from_ledger` is rounded down to the start of its
+    /// bucket.  At most `MAX_STATS_BUCKETS` buckets are read, ending with
+    /// the current one; an earlier `from_ledger` is clamped to that window.
+    pub fn stats_since(env: Env, from_ledger: u32) -> OutcomeStats {
+        let current = env.ledger().sequence() / OUTCOME_BUCKET_LEDGERS;
+        let first = (from_ledger / OUTCOME_BUCKET_LEDGERS)
+            .max(current.saturating_sub(MAX_STATS_BUCKETS - 1));
+        let mut total = OutcomeStats::default();
+        for bucket in first..=current {
+            let Some(stats) = env
+                .storage()
+                .persistent()
+                .get::<_, OutcomeStats>(&DataKey::OutcomeBucket(bucket))
+            else {
+                continue;
+            };
+            total.player1_won = total.player1_won.saturating_add(stats.player1_won);
+            total.player2_won = total.player2_won.saturating_add(stats.player2_won);
+            total.both_found = total.both_found.saturating_add(stats.both_found);
+            total.neither_found = total.neither_found.saturating_add(stats.neither_found);
+        }
+        total
+    }
+
+    /// Find the active session whose `treasure_hash` equals `public_inputs`.
+    ///
+    /// A recovery aid for clients that kept the public inputs but lost the
+    /// session id.  Scans the last `MAX_TARGET_SCAN` positions of the
+    /// active-session index, where new sessions are appended, reading one
+    /// game entry each; sessions further back are not found.
+    pub fn find_session_by_target(env: Env, public_inputs: Bytes) -> Option<u32> {
+        let count = Self::active_session_count(env.clone());
+        let stop = count.saturating_sub(MAX_TARGET_SCAN);
+        for index in (stop..count).rev() {
+            let session_id = Self::active_session_at(&env, index);
+            let Some(game) = env
+                .storage()
+                .temporary()
+                .get::<_, Game>(&DataKey::Game(session_id))
+            else {
+                continue;
+            };
+            if Bytes::from(game.treasure_hash) == public_inputs {
+                return Some(session_id);
+            }
+        }
+        None
+    }
+
+    /// Return the length of the active-session index: started, unresolved
+    /// sessions, including any that expired unresolved.
+    pub fn active_session_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActiveSessionCount)
+            .unwrap_or(0)
+    }
+
+    /// Return the active-session index in position order.  New sessions are
+    /// appended; removing one moves the last entry into its position.
+    ///
+    /// Sessions whose game expired unresolved stay listed (and `get_game`
+    /// returns `GameNotFound` for them) until `prune_expired` drops them.
+    pub fn list_active_sessions(env: Env) -> Vec<u32> {
+        let mut active = Vec::new(&env);
+        for index in 0..Self::active_session_count(env.clone()) {
+            active.push_back(Self::active_session_at(&env, index));
+        }
+        active
+    }
+
+    /// Drop those of up to `MAX_PRUNE_BATCH` listed sessions whose game
+    /// has expired from the active-session index.  Permissionless: live
+    /// sessions and ids not in the index are left alone.
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` – more than `MAX_PRUNE_BATCH` session ids.
+    pub fn prune_expired(env: Env, session_ids: Vec<u32>) -> Result<u32, Error> {
+        if session_ids.len() > MAX_PRUNE_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        let mut pruned = 0;
+        for session_id in session_ids.iter() {
+            if env.storage().temporary().has(&DataKey::Game(session_id)) {
+                continue;
+            }
+            if Self::remove_active_session(&env, session_id) {
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Return the phase, treasure hash and players of up to
+    /// `MAX_LOBBY_BATCH` sessions in one call, in input order.
+    ///
+    /// Missing (never started or expired) sessions yield `None`.
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` – more than `MAX_LOBBY_BATCH` session ids.
+    pub fn get_lobby_view(
+        env: Env,
+        session_ids: Vec<u32>,
+    ) -> Result<Vec<Option<LobbyEntry>>, Error> {
+        if session_ids.len() > MAX_LOBBY_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        let mut entries = Vec::new(&env);
+        for session_id in session_ids.iter() {
+            let entry = env
+                .storage()
+                .temporary()
+                .get::<_, Game>(&DataKey::Game(session_id))
+                .map(|game| LobbyEntry {
+                    phase: Self::game_phase(&game),
+                    treasure_hash: game.treasure_hash,
+                    player1: game.player1,
+                    player2: game.player2,
+                });
+            entries.push_back(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Return a storage footprint estimate for capacity planning.
+    pub fn storage_report(env: Env) -> StorageReport {
+        let counts = Self::phase_counts(env.clone());
+        let active_games = counts.open + counts.one_verified + counts.both_verified;
+        StorageReport {
+            active_games,
+            resolved_games: counts.resolved,
+            active_index_len: Self::active_session_count(env.clone()),
+            session_names: env
+                .storage()
+                .instance()
+                .get(&DataKey::SessionNameCount)
+                .unwrap_or(0),
+            estimated_temporary_entries: active_games.saturating_add(counts.resolved),
+        }
+    }
+
+    /// Number of players waiting in the matchmaking queue.
+    pub fn queue_length(env: Env) -> u32 {
+        Self::match_queue(&env).len()
+    }
+
+    /// Session id `start_game_auto` will allocate next: the first id at or
+    /// after the counter (starting at 1) that holds no game.
+    ///
+    /// # Errors
+    /// * `NoFreeSessionId` – the next `MAX_SESSION_ID_SCAN` ids are all
+    ///   taken, or reach `QUEUE_SESSION_BASE`.  Retry once some of those
+    ///   games are deleted or expire.
+    pub fn peek_next_session_id(env: Env) -> Result<u32, Error> {
+        let first: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextSessionId)
+            .unwrap_or(1);
+        let end = first
+            .saturating_add(MAX_SESSION_ID_SCAN)
+            .min(QUEUE_SESSION_BASE);
+        (first..end)
+            .find(|&id| !env.storage().temporary().has(&DataKey::Game(id)))
+            .ok_or(Error::NoFreeSessionId)
+    }
+
+    /// Session id the next queue match will use; pre-register its target
+    /// with `preregister_target` before a match can start.
+    pub fn next_queue_session(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextQueueSession)
+            .unwrap_or(QUEUE_SESSION_BASE)
+    }
+
+    /// Return the live session between `p1` and `p2` in either seat order,
+    /// if any; the most recently started one when there are several.
+    pub fn active_game_between(env: Env, p1: Address, p2: Address) -> Option<u32> {
+        env.storage().temporary().get(&Self::pair_key(&p1, &p2))
+    }
+
+    /// Return how many games are in each lifecycle phase, in O(1).
+    pub fn phase_counts(env: Env) -> PhaseCounts {
+        env.storage()
+            .instance()
+            .get(&DataKey::PhaseCounts)
+            .unwrap_or_default()
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Whether `who` is the admin.  A cheap read for UIs that show admin
+    /// controls conditionally; the contract has no other roles.
+    pub fn is_admin(env: Env, who: Address) -> bool {
+        Self::get_admin(env) == who
+    }
+
+    /// Propose `new_admin` as the next admin.  Nothing changes until the
+    /// proposed address calls `accept_admin`, so a mistyped address cannot
+    /// lock the contract; proposing again replaces the pending proposal.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+    }
+
+    /// Complete an admin transfer.  Requires the pending admin's auth.
+    ///
+    /// # Errors
+    /// * `NoPendingAdmin` – no transfer has been proposed.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::NoPendingAdmin)?;
+        pending.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &pending);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        Ok(())
+    }
+
+    /// Address proposed by `propose_admin` and not yet accepted, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Return the default Game Hub.
+    ///
+    /// # Errors
+    /// * `NoHubConfigured` – standalone deployment without a hub.
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .ok_or(Error::NoHubConfigured)
+    }
+
+    pub fn set_hub(env: Env, new_hub: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::GameHubAddress, &new_hub);
+    }
+
+    /// Repoint live games at `new_hub`, returning how many were migrated.
+    ///
+    /// Updates the `hub` snapshot of the games at active-index positions
+    /// `offset .. offset + MAX_MIGRATION_BATCH`, so they settle on `new_hub`.
+    /// Page through the index by calling with `offset` = 0, 25, 50, … while
+    /// `offset < active_session_count()`.  Games already on `new_hub` and
+    /// expired sessions are skipped, so re-running a page is harmless.  Does
+    /// not change the default or category hubs.
+    ///
+    /// The old hub's lock id means nothing to `new_hub`, so `hub_lock_id` is
+    /// reset to 0 and migrated games settle through the lock-free calls.
+    pub fn migrate_all_games_to_hub(env: Env, new_hub: Address, offset: u32) -> u32 {
+        Self::require_admin(&env);
+        let end = offset
+            .saturating_add(MAX_MIGRATION_BATCH)
+            .min(Self::active_session_count(env.clone()));
+        let mut migrated = 0;
+        for index in offset.min(end)..end {
+            let session_id = Self::active_session_at(&env, index);
+            let key = DataKey::Game(session_id);
+            let Some(mut game) = env.storage().temporary().get::<_, Game>(&key) else {
+                continue;
+            };
+            if game.hub.as_ref() == Some(&new_hub) {
+                continue;
+            }
+            game.hub = Some(new_hub.clone());
+            game.hub_lock_id = 0;
+            env.storage().temporary().set(&key, &game);
+            migrated += 1;
+        }
+        migrated
+    }
+
+    /// Return the hub registered for `category`, if any.
+    pub fn get_category_hub(env: Env, category: Symbol) -> Option<Address> {
+        Self::category_hubs(&env).get(category)
+    }
+
+    /// Route sessions started under `category` to `hub`.
+    ///
+    /// Only sessions started afterwards use the new route: each game keeps
+    /// the hub it locked its points on.  Move live sessions with
+    /// `migrate_all_games_to_hub`.
+    pub fn set_category_hub(env: Env, category: Symbol, hub: Address) {
+        Self::require_admin(&env);
+        let mut hubs = Self::category_hubs(&env);
+        hubs.set(category, hub);
+        env.storage().instance().set(&DataKey::CategoryHubs, &hubs);
+    }
+
+    /// Remove a category route; its sessions fall back to the default hub.
+    pub fn remove_category_hub(env: Env, category: Symbol) {
+        Self::require_admin(&env);
+        let mut hubs = Self::category_hubs(&env);
+        hubs.remove(category);
+        env.storage().instance().set(&DataKey::CategoryHubs, &hubs);
+    }
+
+    pub fn get_verifier(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifierAddress)
+            .expect("Verifier not set")
+    }
+
+    /// Update the verifier contract address.
+    ///
+    /// ⚠ Verifier Upgrade Warning: if the new verifier embeds a different VK,
+    /// all proofs generated against the old VK will fail.  Coordinate upgrades
+    /// carefully with all active players.
+    ///
+    /// # Errors
+    /// * `NotAContract` – `new_verifier` is not a deployed Wasm contract, so
+    ///   every submission would fail at call time.
+    pub fn set_verifier(env: Env, new_verifier: Address) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if !Self::is_wasm_contract(&new_verifier) {
+            return Err(Error::NotAContract);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VerifierAddress, &new_verifier);
+        Ok(())
+    }
+
+    /// Return the verifier registered under `name`, if any.
+    pub fn get_named_verifier(env: Env, name: Symbol) -> Option<Address> {
+        Self::named_verifiers(&env).get(name)
+    }
+
+    /// Register `verifier` under `name` for games started with
+    /// `GameOptions::verifier`, replacing any verifier already under `name`.
+    ///
+    /// # Errors
+    /// * `NotAContract` – `verifier` is not a deployed Wasm contract.
+    /// * `TooManyVerifiers` – `name` is new and `MaxVerifiers` are registered.
+    pub fn register_verifier(env: Env, name: Symbol, verifier: Address) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if !Self::is_wasm_contract(&verifier) {
+            return Err(Error::NotAContract);
+        }
+        let mut verifiers = Self::named_verifiers(&env);
+        if !verifiers.contains_key(name.clone())
+            && verifiers.len() >= Self::get_max_verifiers(env.clone())
+        {
+            return Err(Error::TooManyVerifiers);
+        }
+        verifiers.set(name, verifier);
+        env.storage()
+            .instance()
+            .set(&DataKey::NamedVerifiers, &verifiers);
+        Ok(())
+    }
+
+    /// Remove the verifier registered under `name`, freeing its slot.
+    ///
+    /// # Errors
+    /// * `UnknownVerifier` – nothing is registered under `name`.
+    /// * `VerifierInUse` – live games still use it.  Games that expired
+    ///   unresolved keep counting, as with `phase_counts`.
+    pub fn unregister_verifier(env: Env, name: Symbol) -> Result<(), Error> {
+        Self::require_admin(&env);
+        let mut verifiers = Self::named_verifiers(&env);
+        if !verifiers.contains_key(name.clone()) {
+            return Err(Error::UnknownVerifier);
+        }
+        let live: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VerifierGames(name.clone()))
+            .unwrap_or(0);
+        if live > 0 {
+            return Err(Error::VerifierInUse);
+        }
+        verifiers.remove(name);
+        env.storage()
+            .instance()
+            .set(&DataKey::NamedVerifiers, &verifiers);
+        Ok(())
+    }
+
+    /// Maximum number of named verifiers.
+    pub fn get_max_verifiers(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxVerifiers)
+            .unwrap_or(DEFAULT_MAX_VERIFIERS)
+    }
+
+    /// Set the named-verifier cap.  Lowering it below the current count only
+    /// blocks new registrations.
+    pub fn set_max_verifiers(env: Env, max: u32) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::MaxVerifiers, &max);
+    }
+
+    /// Resolve a live session immediately from whatever has been submitted,
+    /// recording an optional `reason` for audits.
+    ///
+    /// Unlike `resolve_game` this does not require a submission: a session
+    /// nobody proved resolves to `NeitherFound`.
+    pub fn force_resolve(
+        env: Env,
+        session_id: u32,
+        reason: Option<Symbol>,
+    ) -> Result<Outcome, Error> {
+        Self::require_admin(&env);
+        let mut game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+
+        let outcome = Self::finalize(&env, session_id, &mut game);
+        Self::record_cancel_reason(&env, session_id, &reason);
+        GameForceResolved {
+            session_id,
+            outcome: outcome.clone(),
+            reason,
+        }
+        .publish(&env);
+
+        Ok(outcome)
+    }
+
+    /// Cancel a single wedged session and refund both players.
+    ///
+    /// Works regardless of submission state: recorded proofs are discarded,
+    /// the game is marked resolved as `NeitherFound`, and the hub is asked
+    /// to release both players' points via `refund_game` (or
+    /// `refund_game_with_lock` for a lock-aware hub).  Player results
+    /// and the bridge event are skipped, since nobody played it out.
+    pub fn admin_refund(env: Env, session_id: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+        let game = Self::load_game(&env, session_id)?;
+        Self::check_frozen(&env, &game)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+
+        Self::refund(&env, session_id, game);
+        GameRefunded { session_id, admin }.publish(&env);
+        Ok(())
+    }
+
+    /// Refund a live game whose verifier reports itself paused upstream,
+    /// exactly as `admin_refund` would, so players are not stranded by a
+    /// proof that cannot be verified.
+    ///
+    /// Permissionless, but only while `PausedVerifierRefund` is on and once
+    /// the game has been live for `get_paused_refund_delay` seconds.
+    /// Verifiers without an `is_paused` method never count as paused.
+    ///
+    /// # Errors
+    /// * `PausedRefundDisabled` – the flag is off.
+    /// * `GameAlreadyResolved` – the game is already resolved.
+    /// * `PausedRefundTooEarly` – the delay has not elapsed.
+    /// * `VerifierNotPaused` – the verifier is not paused.
+    pub fn resolve_if_verifier_paused(env: Env, session_id: u32) -> Result<Outcome, Error> {
+        if !Self::is_paused_verifier_refund(env.clone()) {
+            return Err(Error::PausedRefundDisabled);
+        }
+        let game = Self::load_game(&env, session_id)?;
+        if game.resolved {
+            return Err(Error::GameAlreadyResolved);
+        }
+        let delay = Self::get_paused_refund_delay(env.clone());
+        if env.ledger().timestamp() < game.started_at.saturating_add(delay) {
+            return Err(Error::PausedRefundTooEarly);
+        }
+        let verifier = Self::verifier_for(&env, &game);
+        let paused = matches!(
+            PausableVerifierClient::new(&env, &verifier).try_is_paused(),
+            Ok(Ok(true))
+        );
+        if !paused {
+            return Err(Error::VerifierNotPaused);
+        }
+
+        Self::refund(&env, session_id, game);
+        Ok(Outcome::NeitherFound)
+    }
+
+    /// Return the note recorded when a session was cancelled or
+    /// force-resolved, if any.
+    pub fn get_cancel_reason(env: Env, session_id: u32) -> Option<Symbol> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::CancelReason(session_id))
+    }
+
+    /// Return every fee-related setting in one call, so clients can show the
+    /// economics before a player commits.
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        FeeConfig {
+            resolution_reward: Self::get_resolution_reward(env.clone()),
+            players_only_reward: Self::is_players_only_reward(env.clone()),
+            rounding_policy: Self::get_rounding_policy(env),
+        }
+    }
+
+    /// Split `amount` points, giving the winner `winner_bps` / 10 000 of it
+    /// and the protocol the rest, rounded per `get_rounding_policy`.
+    ///
+    /// The single rounding rule for settlement math: the contract currently
+    /// passes stakes to the hub whole, and any fee, split or refund that
+    /// divides points must go through this function.
+    ///
+    /// # Errors
+    /// * `InvalidSplit` – `amount` is negative or `winner_bps` > 10 000.
+    pub fn split_points(env: Env, amount: i128, winner_bps: u32) -> Result<PointsSplit, Error> {
+        if amount < 0 || winner_bps > BPS_DENOMINATOR as u32 {
+            return Err(Error::InvalidSplit);
+        }
+        Ok(match Self::get_rounding_policy(env) {
+            RoundingPolicy::ToProtocol => {
+                let winner = Self::bps_of_floor(amount, winner_bps as i128);
+                PointsSplit {
+                    winner,
+                    protocol: amount - winner,
+                }
+            }
+            RoundingPolicy::ToWinner => {
+                let protocol = Self::bps_of_floor(amount, BPS_DENOMINATOR - winner_bps as i128);
+                PointsSplit {
+                    winner: amount - protocol,
+                    protocol,
+                }
+            }
+        })
+    }
+
+    /// Remainder rule for `split_points`.
+    pub fn get_rounding_policy(env: Env) -> RoundingPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundingPolicy)
+            .unwrap_or_default()
+    }
+
+    /// Set the remainder rule for `split_points`.
+    pub fn set_rounding_policy(env: Env, policy: RoundingPolicy) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoundingPolicy, &policy);
+    }
+
+    /// How submissions to resolved games are handled.
+    pub fn get_late_submit_policy(env: Env) -> LateSubmitPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::LateSubmitPolicy)
+            .unwrap_or_default()
+    }
+
+    /// Set how submissions to resolved games are handled.
+    pub fn set_late_submit_policy(env: Env, policy: LateSubmitPolicy) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::LateSubmitPolicy, &policy);
+    }
+
+    /// Points credited per resolution by `resolve_game_as`.
+    pub fn get_resolution_reward(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ResolutionReward)
+            .unwrap_or(0)
+    }
+
+    /// Set the points credited per resolution; 0 disables the reward.
+    pub fn set_resolution_reward(env: Env, reward: i128) {
+        Self::require_admin(&env);
+        if reward < 0 {
+            panic!("Resolution reward cannot be negative");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ResolutionReward, &reward);
+    }
+
+    /// Whether the resolution reward is restricted to the session's players.
+    pub fn is_players_only_reward(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::PlayersOnlyReward)
+            .unwrap_or(false)
+    }
+
+    /// Restrict the resolution reward to the session's players, or lift the
+    /// restriction.
+    pub fn set_players_only_reward(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PlayersOnlyReward, &enabled);
+    }
+
+    /// ed25519 public key result attestations are verified against, if set.
+    pub fn get_attestation_key(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::AttestationKey)
+    }
+
+    /// Set the ed25519 public key whose signatures `attest_result` accepts.
+    pub fn set_attestation_key(env: Env, key: BytesN<32>) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::AttestationKey, &key);
+    }
+
+    /// Whether the admin has paused new games and submissions.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Halt the contract for incident response: `start_game` and proof
+    /// submissions fail with `ContractPaused`, while resolution, settlement
+    /// and every query keep working so in-flight games can settle.
+    pub fn pause(env: Env) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Paused, &true);
+    }
+
+    /// Lift a `pause`.
+    pub fn unpause(env: Env) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Paused, &false);
+    }
+
+    /// Whether `resolve_if_verifier_paused` is enabled.
+    pub fn is_paused_verifier_refund(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::PausedVerifierRefund)
+            .unwrap_or(false)
+    }
+
+    /// Enable or disable `resolve_if_verifier_paused`.
+    pub fn set_paused_verifier_refund(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PausedVerifierRefund, &enabled);
+    }
+
+    /// Seconds a game must have been live before a paused verifier lets it
+    /// be refunded.
+    pub fn get_paused_refund_delay(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PausedRefundDelay)
+            .unwrap_or(DEFAULT_PAUSED_REFUND_DELAY_SECONDS)
+    }
+
+    /// Set the paused-verifier refund delay in seconds.
+    pub fn set_paused_refund_delay(env: Env, seconds: u64) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PausedRefundDelay, &seconds);
+    }
+
+    /// Whether only players (through `resolve_game_as`) can resolve games.
+    pub fn is_players_only_resolve(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::PlayersOnlyResolve)
+            .unwrap_or(false)
+    }
+
+    /// Restrict resolution to the session's players, or make it
+    /// permissionless again.
+    pub fn set_players_only_resolve(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::PlayersOnlyResolve, &enabled);
+    }
+
+    /// Cap on the points each player may commit, if one is set.
+    pub fn get_max_points(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::MaxPoints)
+    }
+
+    /// Cap on the bytes a submission may hand the verifier, if one is set.
+    pub fn get_max_verifier_input_budget(env: Env) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxVerifierInputBudget)
+    }
+
+    /// Reject submissions whose proof plus public inputs exceed `budget`
+    /// bytes before the verifier is called, so oversized inputs cannot burn
+    /// the verifier's instruction budget.
+    pub fn set_max_verifier_input_budget(env: Env, budget: u32) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxVerifierInputBudget, &budget);
+    }
+
+    /// Longest proof, in bytes, `submit_zk_proof` accepts.
+    pub fn get_max_proof_len(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxProofLen)
+            .unwrap_or(DEFAULT_MAX_PROOF_LEN)
+    }
+
+    /// Reject proofs longer than `max_len` bytes with `ProofTooLarge`
+    /// before they are hashed or handed to the verifier.
+    pub fn set_max_proof_len(env: Env, max_len: u32) {
+        Self::require_admin(&env);
+        if max_len == 0 {
+            panic!("Proof length cap must be at least 1");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxProofLen, &max_len);
+    }
+
+    /// Set the global points cap for future sessions.
+    ///
+    /// # Errors
+    /// * `InvalidPointsCap` – `max` is not positive or exceeds
+    ///   `MAX_POINTS_CEILING`.
+    pub fn set_max_points(env: Env, max: i128) -> Result<(), Error> {
+        Self::require_admin(&env);
+        Self::check_points_cap(max)?;
+        env.storage().instance().set(&DataKey::MaxPoints, &max);
+        Ok(())
+    }
+
+    /// Grant `session_id` its own points cap, replacing the global cap when
+    /// the session starts.  Grant it before calling `start_game`.
+    ///
+    /// # Errors
+    /// * `InvalidPointsCap` – `max` is not positive or exceeds
+    ///   `MAX_POINTS_CEILING`.
+    pub fn set_game_max_points(env: Env, session_id: u32, max: i128) -> Result<(), Error> {
+        Self::require_admin(&env);
+        Self::check_points_cap(max)?;
+        let key = DataKey::GameMaxPoints(session_id);
+        env.storage().temporary().set(&key, &max);
+        let ttl = Self::get_game_ttl(env.clone());
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Move a live game from `old_id` to `new_id`.  Admin only.
+    ///
+    /// The nullifier is re-derived for `new_id`, so the game takes
+    /// `treasure_hash`, the target computed off-chain from the new
+    /// nullifier.  Only allowed before any proof is accepted.  The player
+    /// session indices, the active-session index and session names follow
+    /// the game; a points cap or target granted for `old_id` is dropped.
+    /// The hub keeps settling it under
+    /// its original id, which cannot be started again until the game is
+    /// deleted or expires.
+    ///
+    /// # Errors
+    /// * `SessionExists` – a game already exists at `new_id`, or the hub
+    ///   still settles a moved game under it.
+    /// * `ReservedSessionId` – `new_id` is in the `join_queue` range.
+    /// * `SubmissionsRecorded` – a proof was already accepted.
+    pub fn rekey_game(
+        env: Env,
+        old_id: u32,
+        new_id: u32,
+        treasure_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env);
+        let mut game = Self::load_game(&env, old_id)?;
+        Self::check_frozen(&env, &game)?;
+        if new_id >= QUEUE_SESSION_BASE {
+            return Err(Error::ReservedSessionId);
+        }
+        if env.storage().temporary().has(&DataKey::Game(new_id))
+            || (new_id != game.hub_session_id && Self::hub_session_moved(&env, new_id))
+        {
+            return Err(Error::SessionExists);
+        }
+        if game.submissions > 0 || game.resolved {
+            return Err(Error::SubmissionsRecorded);
+        }
+
+        game.nullifier = Self::compute_nullifier(
+            &env,
+            game.derivation_version,
+            new_id,
+            &game.player1,
+            &game.player2,
+            game.deadline_ledger,
+        );
+        game.treasure_hash = treasure_hash;
+        let ttl = Self::get_game_ttl(env.clone());
+        game.live_until_ledger = env.ledger().sequence().saturating_add(ttl);
+        let key = DataKey::Game(new_id);
+        env.storage().temporary().set(&key, &game);
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        env.storage().temporary().remove(&DataKey::Game(old_id));
+        let moved_key = DataKey::HubSessionMoved(game.hub_session_id);
+        if new_id == game.hub_session_id {
+            env.storage().persistent().remove(&moved_key);
+        } else {
+            env.storage().persistent().set(&moved_key, &new_id);
+        }
+        // Start-time settings granted for `old_id` must not apply to its
+        // next game.
+        env.storage()
+            .temporary()
+            .remove(&DataKey::GameMaxPoints(old_id));
+        env.storage()
+            .temporary()
+            .remove(&DataKey::PreregisteredTarget(old_id));
+
+        let slot_key = DataKey::ActiveSessionSlot(old_id);
+        if let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) {
+            env.storage().persistent().remove(&slot_key);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ActiveSessionSlot(new_id), &slot);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ActiveSessionAt(slot), &new_id);
+        }
+        for player in [&game.player1, &game.player2] {
+            let key = DataKey::PlayerSessions(player.clone());
+            let mut sessions = Self::get_player_sessions(env.clone(), player.clone());
+            if let Some(index) = sessions.first_index_of(old_id) {
+                sessions.set(index, new_id);
+                env.storage().persistent().set(&key, &sessions);
+            }
+        }
+        let pair_key = Self::pair_key(&game.player1, &game.player2);
+        if env.storage().temporary().get(&pair_key) == Some(old_id) {
+            env.storage().temporary().set(&pair_key, &new_id);
+        }
+        if let Some(name) = game.name {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SessionName(name), &new_id);
+        }
+        Ok(())
+    }
+
+    /// Commit the treasure hash `session_id` will use, overriding the one
+    /// passed to `start_game`.  Consumed when the session starts.
+    ///
+    /// # Errors
+    /// * `SessionExists` – the session has already started.
+    pub fn preregister_target(env: Env, session_id: u32, target: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if env.storage().temporary().has(&DataKey::Game(session_id)) {
+            return Err(Error::SessionExists);
+        }
+        let key = DataKey::PreregisteredTarget(session_id);
+        env.storage().temporary().set(&key, &target);
+        let ttl = Self::get_game_ttl(env.clone());
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Whether resolved games are frozen (see `set_frozen_resolved`).
+    pub fn is_frozen_resolved(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::FrozenResolved)
+            .unwrap_or(false)
+    }
+
+    /// Freeze resolved games: every mutating call on one (submissions,
+    /// chunk uploads, `resolve_game`, `force_resolve`, `admin_refund`,
+    /// `rekey_game`, `cancel_game`, `claim_timeout`, `extend_session`)
+    /// fails uniformly with `GameFrozen`, while queries keep working.  The
+    /// state lasts until `reclaim_game` deletes the game; `claim_winnings`
+    /// and `sweep_unclaimed` still settle it.
+    pub fn set_frozen_resolved(env: Env, frozen: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::FrozenResolved, &frozen);
+    }
+
+    /// Whether the matchmaking queue only pairs equal point commitments.
+    pub fn is_require_equal_points(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::RequireEqualPoints)
+            .unwrap_or(false)
+    }
+
+    /// Make the matchmaking queue pair only players committing equal points.
+    pub fn set_require_equal_points(env: Env, required: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::RequireEqualPoints, &required);
+    }
+
+    /// Whether exact retries of an accepted submission succeed as no-ops.
+    pub fn is_idempotent_submit(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::IdempotentSubmit)
+            .unwrap_or(false)
+    }
+
+    /// Make a repeat `submit_zk_proof` with the same proof, energy and public
+    /// inputs return `Ok(None)` instead of `AlreadySubmitted`.
+    pub fn set_idempotent_submit(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::IdempotentSubmit, &enabled);
+    }
+
+    /// Whether both players' proofs must have different bytes.
+    pub fn is_require_distinct_proofs(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::RequireDistinctProofs)
+            .unwrap_or(false)
+    }
+
+    /// Require or allow both players submitting identical proof bytes.
+    pub fn set_require_distinct_proofs(env: Env, required: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::RequireDistinctProofs, &required);
+    }
+
+    /// Whether winners must claim before their game settles on the hub.
+    pub fn is_claim_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ClaimRequired)
+            .unwrap_or(false)
+    }
 
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
+    /// Require or stop requiring winners to claim.  Applies to games
+    /// resolved from now on.
+    pub fn set_claim_required(env: Env, required: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimRequired, &required);
+    }
+
+    /// Whether `reclaim_game` keeps the outcome queryable.
+    pub fn is_keep_outcome_after_reclaim(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::KeepOutcomeAfterReclaim)
+            .unwrap_or(false)
+    }
+
+    /// Keep or drop outcomes of games reclaimed from now on.
+    pub fn set_keep_outcome_after_reclaim(env: Env, keep: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::KeepOutcomeAfterReclaim, &keep);
+    }
+
+    /// Ledgers after resolution before an unclaimed game can be swept.
+    pub fn get_claim_window(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(DEFAULT_CLAIM_WINDOW_LEDGERS)
+    }
+
+    /// Set the claim window in ledgers.
+    pub fn set_claim_window(env: Env, ledgers: u32) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimWindow, &ledgers);
+    }
+
+    /// Ledgers after resolution during which a player can `escalate`.
+    pub fn get_escalation_window(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EscalationWindow)
+            .unwrap_or(DEFAULT_ESCALATION_WINDOW_LEDGERS)
+    }
+
+    /// Set the escalation window in ledgers.
+    pub fn set_escalation_window(env: Env, ledgers: u32) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscalationWindow, &ledgers);
+    }
+
+    /// Ledgers after the first verification during which resolution is
+    /// blocked; 0 (the default) disables the window.
+    pub fn get_second_player_window(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SecondPlayerWindow)
+            .unwrap_or(0)
+    }
+
+    /// Set the second-player window in ledgers.
+    pub fn set_second_player_window(env: Env, ledgers: u32) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::SecondPlayerWindow, &ledgers);
+    }
+
+    /// Ledgers a game entry is kept alive for at start, on each submission
+    /// and by `extend_session`.
+    pub fn get_game_ttl(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::GameTtl)
+            .unwrap_or(DEFAULT_GAME_TTL_LEDGERS)
+    }
+
+    /// Set the game TTL for entries written from now on; live games keep
+    /// their current expiry until next extended.
+    ///
+    /// # Errors
+    /// * `InvalidTtl` – `ledgers` is below `MIN_GAME_TTL_LEDGERS` or above
+    ///   the network's maximum entry TTL.
+    pub fn set_game_ttl(env: Env, ledgers: u32) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if !(MIN_GAME_TTL_LEDGERS..=env.storage().max_ttl()).contains(&ledgers) {
+            return Err(Error::InvalidTtl);
+        }
+        env.storage().instance().set(&DataKey::GameTtl, &ledgers);
+        Ok(())
+    }
+
+    /// Whether submissions extend TTL in proportion to the remaining budget.
+    pub fn is_proportional_ttl(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProportionalTtl)
+            .unwrap_or(false)
+    }
+
+    /// Turn proportional TTL extension on submission on or off.
+    pub fn set_proportional_ttl(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProportionalTtl, &enabled);
+    }
+
+    /// Ledgers a resolved session id must rest before it can be reused.
+    pub fn get_session_reuse_cooldown(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SessionReuseCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Set the reuse cooldown in ledgers; 0 allows immediate reuse.
+    pub fn set_session_reuse_cooldown(env: Env, ledgers: u32) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::SessionReuseCooldown, &ledgers);
+    }
+
+    /// `VerifierMode` applied to sessions started without an override.
+    pub fn get_verifier_mode(env: Env) -> VerifierMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifierMode)
+            .unwrap_or(VerifierMode::Trap)
+    }
+
+    /// Set the default `VerifierMode` for future sessions.  Live sessions
+    /// keep the mode they were started with.
+    pub fn set_verifier_mode(env: Env, mode: VerifierMode) {
+        Self::require_admin(&env);
+        if mode == VerifierMode::Inherit {
+            panic!("Global verifier mode must be Trap or Bool");
+        }
+        env.storage().instance().set(&DataKey::VerifierMode, &mode);
+    }
+
+    /// `GameMode` applied to sessions started without an explicit mode.
+    pub fn get_default_game_mode(env: Env) -> GameMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::DefaultGameMode)
+            .unwrap_or(GameMode::LowestEnergy)
+    }
+
+    /// Set the default `GameMode` for future sessions.  Live sessions keep
+    /// the mode they were started with.
+    pub fn set_default_game_mode(env: Env, mode: GameMode) {
+        Self::require_admin(&env);
+        if mode == GameMode::Inherit {
+            panic!("Default game mode must be LowestEnergy, FirstWins or EarliestVerified");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DefaultGameMode, &mode);
+    }
+
+    /// `TieDefault` snapshotted onto sessions started from now on.
+    pub fn get_tie_default(env: Env) -> TieDefault {
+        env.storage()
+            .instance()
+            .get(&DataKey::TieDefault)
+            .unwrap_or_default()
+    }
+
+    /// Set the `TieDefault` snapshotted onto future sessions.  Ties are
+    /// always settled as draws; the value no longer affects settlement.
+    pub fn set_tie_default(env: Env, tie_default: TieDefault) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::TieDefault, &tie_default);
+    }
+
+    /// Whether mismatched submissions are recorded for debugging.
+    pub fn is_debug_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::DebugMode)
+            .unwrap_or(false)
+    }
+
+    /// Turn debug recording of mismatched submissions on or off.  Leave off
+    /// in production: it stores extra bytes per game.  `submit_zk_proof`
+    /// still fails on a mismatch either way; recording goes through
+    /// `record_rejected_inputs`.
+    pub fn set_debug_mode(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::DebugMode, &enabled);
+    }
+
+    /// Whether games resolve automatically on the second verification.
+    pub fn is_auto_resolve(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoResolve)
+            .unwrap_or(false)
+    }
+
+    /// Turn automatic resolution on the second verification on or off.
+    pub fn set_auto_resolve(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::AutoResolve, &enabled);
+    }
+
+    /// Whether settlements report both stakes via `settle_with_stakes`.
+    pub fn is_stake_aware_settlement(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::StakeAwareSettlement)
+            .unwrap_or(false)
+    }
+
+    /// Switch settlement between `settle_with_stakes` and
+    /// `end_game_with_outcome`.
+    ///
+    /// The hub of every category must implement `settle_with_stakes` before
+    /// this is enabled.  Games nobody won always use `end_game_with_outcome`.
+    pub fn set_stake_aware_settlement(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakeAwareSettlement, &enabled);
+    }
+
+    /// Whether new sessions derive their nullifier with `DOMAIN_TAG`.
+    pub fn is_domain_separation_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::DomainSeparation)
+            .unwrap_or(false)
+    }
+
+    /// Switch new sessions to (or back from) domain-separated derivation.
+    ///
+    /// Live sessions keep the derivation version they were started with.
+    /// Enabling this changes every nullifier from then on, so frontends must
+    /// derive through `derive_nullifier` rather than a hard-coded preimage.
+    pub fn set_domain_separation(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::DomainSeparation, &enabled);
+    }
+
+    /// Whether only allowlisted players may start games.
+    pub fn is_eligibility_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::EligibilityRequired)
+            .unwrap_or(false)
+    }
+
+    /// Turn eligibility gating on or off.
+    pub fn set_eligibility_required(env: Env, required: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::EligibilityRequired, &required);
+    }
+
+    /// Whether `player` is on the allowlist.
+    pub fn is_eligible(env: Env, player: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Eligible(player))
+    }
+
+    /// Add up to `MAX_ELIGIBILITY_BATCH` players to the allowlist.
+    pub fn add_eligible_many(env: Env, players: Vec<Address>) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if players.len() > MAX_ELIGIBILITY_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        for player in players.iter() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Eligible(player), &true);
+        }
+        Ok(())
+    }
+
+    /// Remove up to `MAX_ELIGIBILITY_BATCH` players from the allowlist.
+    pub fn remove_eligible_many(env: Env, players: Vec<Address>) -> Result<(), Error> {
+        Self::require_admin(&env);
+        if players.len() > MAX_ELIGIBILITY_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+        for player in players.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Eligible(player));
+        }
+        Ok(())
+    }
+
+    /// Submission cap applied to sessions started from now on.
+    pub fn get_max_submissions(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxSubmissions)
+            .unwrap_or(DEFAULT_MAX_SUBMISSIONS)
+    }
+
+    /// Set the submission cap for future sessions.  Live sessions keep the
+    /// cap they were started with.
+    pub fn set_max_submissions(env: Env, max_submissions: u32) {
+        Self::require_admin(&env);
+        if max_submissions == 0 {
+            panic!("Submission cap must be at least 1");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxSubmissions, &max_submissions);
+    }
+
+    /// Cap on the number of sessions kept in each player's index.
+    pub fn get_max_player_sessions(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxPlayerSessions)
+            .unwrap_or(DEFAULT_MAX_PLAYER_SESSIONS)
+    }
+
+    /// Set the per-player session index cap.  Indexes longer than the new
+    /// cap are trimmed on their next append.
+    pub fn set_max_player_sessions(env: Env, max_sessions: u32) {
+        Self::require_admin(&env);
+        if max_sessions == 0 {
+            panic!("Player session cap must be at least 1");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPlayerSessions, &max_sessions);
+    }
+
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Version of the running contract code (`VERSION`).
+    pub fn get_version() -> u32 {
+        VERSION
+    }
+
+    /// Storage schema version of this instance: `VERSION` once migrated,
+    /// 0 for an instance deployed before versioning.
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+
+    /// Bring this instance's storage up to `VERSION` after an `upgrade`.
+    ///
+    /// Field backfills for older schemas belong here, keyed on
+    /// `get_schema_version`.  No stored data needs rewriting yet, so this
+    /// only records the schema version; on a current instance it does
+    /// nothing.
+    pub fn migrate(env: Env) {
+        Self::require_admin(&env);
+        if Self::get_schema_version(env.clone()) >= VERSION {
+            return;
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &VERSION);
+    }
+
+    // ========================================================================
+    // Private Helpers
+    // ========================================================================
+
+    fn load_game(env: &Env, session_id: u32) -> Result<Game, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)
+    }
+
+    /// Reject mutation of a resolved game under `FrozenResolved`.
+    fn check_frozen(env: &Env, game: &Game) -> Result<(), Error> {
+        if game.resolved && Self::is_frozen_resolved(env.clone()) {
+            return Err(Error::GameFrozen);
+        }
+        Ok(())
+    }
+
+    /// Remove a resolved game, keeping its outcome under
+    /// `KeepOutcomeAfterReclaim`.
+    fn delete_game(env: &Env, session_id: u32, game: &Game) {
+        if Self::is_keep_outcome_after_reclaim(env.clone()) {
+            let key = DataKey::ReclaimedOutcome(session_id);
+            env.storage()
+                .temporary()
+                .set(&key, &Self::game_outcome(game));
+            let ttl = Self::get_game_ttl(env.clone());
+            env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        }
+        env.storage().temporary().remove(&DataKey::Game(session_id));
+        env.storage()
             .temporary()
+            .remove(&DataKey::GameMaxPoints(session_id));
+        Self::release_game_bindings(env, session_id, game);
+    }
+
+    /// Unbind the name `start_game_named` gave `game`, if any, and the
+    /// `HubSessionMoved` marker `rekey_game` left for it.
+    fn release_game_bindings(env: &Env, session_id: u32, game: &Game) {
+        if game.hub_session_id != session_id {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::HubSessionMoved(game.hub_session_id));
+        }
+        if let Some(name) = &game.name {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::SessionName(name.clone()));
+            Self::update_session_name_count(env, |count| count.saturating_sub(1));
+        }
+    }
+
+    /// True while a game `rekey_game` moved away from `hub_session_id` still
+    /// exists and so still settles under that id.
+    fn hub_session_moved(env: &Env, hub_session_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HubSessionMoved(hub_session_id))
+            .and_then(|session_id| Self::load_game(env, session_id).ok())
+            .is_some_and(|game| game.hub_session_id == hub_session_id)
+    }
+
+    /// True while a player of resolved, arbitrated `game` can still
+    /// `escalate` it.
+    fn escalation_open(env: &Env, game: &Game) -> bool {
+        let window_end = game
+            .resolved_ledger
+            .unwrap_or(0)
+            .saturating_add(Self::get_escalation_window(env.clone()));
+        game.arbiter.is_some() && !game.arbitrated && env.ledger().sequence() < window_end
+    }
+
+    /// Load a game that must already be resolved.
+    fn load_resolved_game(env: &Env, session_id: u32) -> Result<Game, Error> {
+        let game = Self::load_game(env, session_id)?;
+        if !game.resolved {
+            return Err(Error::GameNotResolved);
+        }
+        Ok(game)
+    }
+
+    /// Hash the nullifier preimage for the given derivation version.
+    fn compute_nullifier(
+        env: &Env,
+        version: u32,
+        session_id: u32,
+        player1: &Address,
+        player2: &Address,
+        deadline_ledger: Option<u32>,
+    ) -> BytesN<32> {
+        let preimage =
+            Self::nullifier_preimage(env, version, session_id, player1, player2, deadline_ledger);
+        env.crypto().keccak256(&preimage).into()
+    }
+
+    /// Build the nullifier preimage (see Nullifier Design).
+    fn nullifier_preimage(
+        env: &Env,
+        version: u32,
+        session_id: u32,
+        player1: &Address,
+        player2: &Address,
+        deadline_ledger: Option<u32>,
+    ) -> Bytes {
+        let mut preimage = Bytes::new(env);
+        if version == DERIVATION_V1 {
+            preimage.extend_from_slice(DOMAIN_TAG);
+        }
+        preimage.extend_from_array(&session_id.to_be_bytes());
+        preimage.append(&player1.to_string().to_bytes());
+        preimage.append(&player2.to_string().to_bytes());
+        preimage.append(&env.current_contract_address().to_string().to_bytes());
+        if let Some(deadline) = deadline_ledger {
+            preimage.extend_from_array(&deadline.to_be_bytes());
+        }
+        preimage
+    }
+
+    /// Append `session_id` to the verifier's session list, once per session,
+    /// evicting the oldest entry when the list is full.
+    fn record_verifier_session(env: &Env, verifier: &Address, session_id: u32) {
+        let key = DataKey::VerifierSessions(verifier.clone());
+        let mut sessions: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if sessions.contains(session_id) {
+            return;
+        }
+        if sessions.len() >= MAX_VERIFIER_SESSIONS {
+            sessions.pop_front();
+        }
+        sessions.push_back(session_id);
+        env.storage().persistent().set(&key, &sessions);
+    }
+
+    fn record_verifier_stats(env: &Env, verifier: &Address, proof_len: u32) {
+        let key = DataKey::VerifierStats(verifier.clone());
+        let mut stats = Self::verifier_stats(env.clone(), verifier.clone());
+        stats.min_len = if stats.count == 0 {
+            proof_len
+        } else {
+            stats.min_len.min(proof_len)
+        };
+        stats.max_len = stats.max_len.max(proof_len);
+        stats.count = stats.count.saturating_add(1);
+        stats.sum_len = stats.sum_len.saturating_add(proof_len as u64);
+        env.storage().persistent().set(&key, &stats);
+    }
+
+    /// Append `session_id` to `player`'s session index.
+    ///
+    /// While the index is at its cap, the oldest session that is resolved
+    /// (or whose game has expired) is evicted first; if every listed session
+    /// is still live, the oldest entry is evicted instead so the append
+    /// never fails.
+    fn record_player_session(env: &Env, player: &Address, session_id: u32) {
+        let key = DataKey::PlayerSessions(player.clone());
+        let mut sessions: Vec<u32> = env
+            .storage()
+            .persistent()
             .get(&key)
-            .ok_or(Error::GameNotFound)?;
+            .unwrap_or(Vec::new(env));
+        let cap = Self::get_max_player_sessions(env.clone());
+        while sessions.len() >= cap {
+            let evict = sessions
+                .iter()
+                .position(|id| Self::load_game(env, id).map_or(true, |g| g.resolved))
+                .unwrap_or(0) as u32;
+            sessions.remove(evict);
+        }
+        sessions.push_back(session_id);
+        env.storage().persistent().set(&key, &sessions);
+    }
+
+    /// Run every non-verifier precondition of `submit_zk_proof`, in order.
+    ///
+    /// Returns whether `player` is player1 on success.
+    fn check_submission(
+        env: &Env,
+        game: &Game,
+        player: &MuxedAddress,
+        public_inputs: &Bytes,
+    ) -> Result<bool, Error> {
+        if let Some(error) = Self::submission_failures(env, game, player, public_inputs).first() {
+            return Err(error);
+        }
+        Ok(player.address() == game.player1)
+    }
+
+    /// Every failing non-verifier precondition of `submit_zk_proof`, in the
+    /// order `check_submission` reports them.
+    fn submission_failures(
+        env: &Env,
+        game: &Game,
+        player: &MuxedAddress,
+        public_inputs: &Bytes,
+    ) -> Vec<Error> {
+        let mut failures = Vec::new(env);
+        if Self::is_paused(env.clone()) {
+            failures.push_back(Error::ContractPaused);
+        }
+        if game.resolved {
+            failures.push_back(Error::GameAlreadyResolved);
+        }
+
+        let account = player.address();
+        let is_player1 = account == game.player1;
+        let is_player2 = account == game.player2;
+
+        if !is_player1 && !is_player2 {
+            failures.push_back(if player.id().is_some() {
+                Error::MuxedAddressMismatch
+            } else {
+                Error::NotPlayer
+            });
+        }
+        if (is_player1 && game.player1_energy.is_some())
+            || (is_player2 && game.player2_energy.is_some())
+        {
+            failures.push_back(Error::AlreadySubmitted);
+        }
+        if game.submissions >= game.max_submissions {
+            failures.push_back(Error::SubmissionBudgetExhausted);
+        }
+        if let Some(limit) = game.time_limit_seconds {
+            if env.ledger().timestamp() > game.started_at.saturating_add(limit) {
+                failures.push_back(Error::TimeLimitExceeded);
+            }
+        }
+        if Self::deadline_passed(env, game) {
+            failures.push_back(Error::DeadlinePassed);
+        }
+
+        // Validate public_inputs against the session's treasure hash.
+        // This is the sole on-chain binding: an opaque byte equality check.
+        // No field parsing, no byte-offset slicing.
+        let expected = Bytes::from_array(env, &game.treasure_hash.to_array());
+        if *public_inputs != expected {
+            failures.push_back(Error::PublicInputMismatch);
+        }
+
+        failures
+    }
+
+    /// Publish `GameExpired` if the game's time limit has passed and it has
+    /// not been announced yet.  The caller persists `game`.
+    fn announce_expiry(env: &Env, session_id: u32, game: &mut Game) {
+        let time_up = game
+            .time_limit_seconds
+            .is_some_and(|limit| env.ledger().timestamp() > game.started_at.saturating_add(limit));
+        if game.expiry_announced || !(time_up || Self::deadline_passed(env, game)) {
+            return;
+        }
+        game.expiry_announced = true;
+        GameExpired {
+            session_id,
+            ledger: env.ledger().sequence(),
+        }
+        .publish(env);
+    }
+
+    /// Shared body of `resolve_game` and `resolve_game_as`; `by_player`
+    /// passes the `PlayersOnlyResolve` gate.
+    fn resolve(env: &Env, session_id: u32, by_player: bool) -> Result<Outcome, Error> {
+        let mut game = Self::load_game(env, session_id)?;
+        Self::check_frozen(env, &game)?;
+
+        // Idempotent: recompute from stored energy values without re-calling GameHub.
+        if game.resolved {
+            return Ok(Self::game_outcome(&game));
+        }
+        Self::check_resolution(env, &game, by_player)?;
+
+        Ok(Self::finalize(env, session_id, &mut game))
+    }
 
+    /// `resolve` for `resolve_many`: the hub is reported to first, through
+    /// `try_notify_hub`, so a trapping hub fails only this session.
+    fn resolve_isolated(env: &Env, session_id: u32) -> Result<Outcome, Error> {
+        let mut game = Self::load_game(env, session_id)?;
+        Self::check_frozen(env, &game)?;
         if game.resolved {
-            return Err(Error::GameAlreadyResolved);
+            return Ok(Self::game_outcome(&game));
         }
+        Self::check_resolution(env, &game, false)?;
 
-        let is_player1 = player == game.player1;
-        let is_player2 = player == game.player2;
+        let winner = Self::reported_winner(&game, &Self::game_outcome(&game));
+        if !(winner.is_some() && Self::is_claim_required(env.clone())) {
+            Self::try_notify_hub(env, &game, winner)?;
+        }
+        Ok(Self::commit_resolution(env, session_id, &mut game, true))
+    }
 
-        if !is_player1 && !is_player2 {
-            return Err(Error::NotPlayer);
+    /// Checks a live game must pass before `resolve` settles it.
+    fn check_resolution(env: &Env, game: &Game, by_player: bool) -> Result<(), Error> {
+        if !by_player && Self::is_players_only_resolve(env.clone()) {
+            return Err(Error::ResolutionGated);
         }
-        if is_player1 && game.player1_energy.is_some() {
-            return Err(Error::AlreadySubmitted);
+
+        // A forfeited game is already decided.
+        if game.forfeited_by.is_some() {
+            return Ok(());
         }
-        if is_player2 && game.player2_energy.is_some() {
-            return Err(Error::AlreadySubmitted);
+        // Need at least one verified player before resolving.
+        if game.player1_energy.is_none() && game.player2_energy.is_none() {
+            return Err(Error::NeitherPlayerSubmitted);
         }
+        if Self::second_player_window_open(env, game) {
+            return Err(Error::SecondPlayerWindowOpen);
+        }
+        Ok(())
+    }
 
-        // Validate public_inputs against the session's treasure hash.
-        // This is the sole on-chain binding: an opaque byte equality check.
-        // No field parsing, no byte-offset slicing.
-        let expected = Bytes::from_array(&env, &game.treasure_hash.to_array());
-        if public_inputs != expected {
-            return Err(Error::PublicInputMismatch);
+    /// True while only one player has verified and fewer than
+    /// `SecondPlayerWindow` ledgers have passed since that verification.
+    fn second_player_window_open(env: &Env, game: &Game) -> bool {
+        if game.player1_energy.is_some() && game.player2_energy.is_some() {
+            return false;
         }
+        let Some(first) = game.first_verified_ledger else {
+            return false;
+        };
+        let window = Self::get_second_player_window(env.clone());
+        env.ledger().sequence() < first.saturating_add(window)
+    }
 
-        // Cross-contract call: decoupled, stateless UltraHonk verifier.
-        // If the proof is invalid the verifier MUST trap — the whole tx reverts.
-        //
-        // ⚠ Parameter order: the verifier expects (public_inputs, proof_bytes).
-        // Do NOT swap — passing proof as the first argument causes the verifier
-        // to interpret raw proof bytes as Bn254 field elements, which fails
-        // cryptographically and produces Error(Contract, #3 VerificationFailed).
-        let verifier_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::VerifierAddress)
-            .expect("Verifier not set");
-        let verifier = UltraHonkVerifierClient::new(&env, &verifier_addr);
-        verifier.verify_proof(&public_inputs, &proof);
+    /// Mark `game` resolved, persist it and report the outcome to its Game Hub.
+    ///
+    /// Shared by every path that settles a session.
+    fn finalize(env: &Env, session_id: u32, game: &mut Game) -> Outcome {
+        Self::commit_resolution(env, session_id, game, false)
+    }
 
-        // Proof accepted — record player's energy expenditure.
-        if is_player1 {
-            game.player1_energy = Some(energy_used);
-        } else {
-            game.player2_energy = Some(energy_used);
-        }
-        env.storage().temporary().set(&key, &game);
+    /// Body of `finalize`; `hub_reported` skips the hub call for
+    /// `resolve_isolated`, which has already made it.
+    fn commit_resolution(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        hub_reported: bool,
+    ) -> Outcome {
+        let outcome = Self::game_outcome(game);
+        let winner = Self::reported_winner(game, &outcome);
+        let player1_won = winner.as_ref() == Some(&game.player1);
+        let player2_won = winner.as_ref() == Some(&game.player2);
+
+        Self::announce_expiry(env, session_id, game);
+        game.resolved = true;
+        game.resolved_ledger = Some(env.ledger().sequence());
+        game.resolved_at = Some(env.ledger().timestamp());
+        game.awaiting_claim = winner.is_some() && Self::is_claim_required(env.clone());
         env.storage()
             .temporary()
-            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            .set(&DataKey::Game(session_id), game);
+        let verified = game.player1_energy.is_some() as u32 + game.player2_energy.is_some() as u32;
+        Self::retire_live_game(env, session_id, game, verified);
 
-        // Emit an event so the Soroban SDK correctly recognizes this as a state-mutating transaction
-        // instead of silently skipping submission in `isStillReadOnly` fallback.
-        env.events().publish(
-            (soroban_sdk::symbol_short!("submit"), session_id),
-            energy_used,
-        );
+        if !game.awaiting_claim && !hub_reported {
+            Self::notify_hub(env, game, winner);
+        }
 
-        Ok(())
+        Self::record_player_result(env, &game.player1, session_id, player1_won);
+        Self::record_player_result(env, &game.player2, session_id, player2_won);
+        let (player1_result, player2_result) = match outcome {
+            Outcome::Player1Won => (Ordering::Greater, Ordering::Less),
+            Outcome::Player2Won => (Ordering::Less, Ordering::Greater),
+            Outcome::BothFoundTreasure => (Ordering::Equal, Ordering::Equal),
+            Outcome::NeitherFound => (Ordering::Less, Ordering::Less),
+        };
+        Self::record_player_stats(env, &game.player1, player1_result);
+        Self::record_player_stats(env, &game.player2, player2_result);
+        Self::record_outcome_stats(env, &outcome);
+
+        GameResolved {
+            session_id,
+            outcome: outcome.clone(),
+        }
+        .publish(env);
+        BridgeResult {
+            payload: Self::bridge_payload(env, session_id, game, &outcome, player1_won),
+        }
+        .publish(env);
+
+        outcome
     }
 
-    /// Resolve the game and report the outcome to the Game Hub.
-    ///
-    /// Can be called by anyone (permissionless).  Idempotent after first call.
-    /// Requires at least one player to have submitted a proof.
-    ///
-    /// ## Winner Resolution
-    ///
-    /// | p1_energy     | p2_energy     | Outcome            | GameHub            |
-    /// |---------------|---------------|--------------------|--------------------|
-    /// | Some(e1)      | None          | Player1Won         | player1_won = true |
-    /// | None          | Some(e2)      | Player2Won         | player1_won = false|
-    /// | Some(e1)      | Some(e2), e1 < e2 | Player1Won    | player1_won = true |
-    /// | Some(e1)      | Some(e2), e2 < e1 | Player2Won    | player1_won = false|
-    /// | Some(e1)      | Some(e2), e1 == e2 | BothFoundTreasure | player1_won = true |
-    /// | None          | None          | Error: NeitherPlayerSubmitted | – |
+    /// Move a just-resolved game with `verified` accepted proofs out of the
+    /// live phase counts, its named verifier's live games, the player-pair
+    /// index and the active-session index.
+    fn retire_live_game(env: &Env, session_id: u32, game: &Game, verified: u32) {
+        Self::drop_live_game(env, session_id, game);
+        Self::update_phase_counts(env, |c| {
+            match verified {
+                0 => c.open = c.open.saturating_sub(1),
+                1 => c.one_verified = c.one_verified.saturating_sub(1),
+                _ => c.both_verified = c.both_verified.saturating_sub(1),
+            }
+            c.resolved += 1;
+        });
+    }
+
+    /// Remove a game leaving the live phase from its named verifier's live
+    /// games, the player-pair index and the active-session index.
+    fn drop_live_game(env: &Env, session_id: u32, game: &Game) {
+        if let Some(name) = &game.verifier {
+            Self::update_verifier_games(env, name, -1);
+        }
+        let pair_key = Self::pair_key(&game.player1, &game.player2);
+        if env.storage().temporary().get(&pair_key) == Some(session_id) {
+            env.storage().temporary().remove(&pair_key);
+        }
+
+        Self::remove_active_session(env, session_id);
+    }
+
+    /// Report a resolved game's result to its Game Hub, aborting the call
+    /// with `HubCallFailed` if the hub traps.
     ///
-    /// # Arguments
-    /// * `session_id` – The session to resolve.
-    pub fn resolve_game(env: Env, session_id: u32) -> Result<Outcome, Error> {
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
+    /// Standalone games (no hub) settle purely in contract state.
+    fn notify_hub(env: &Env, game: &Game, winner: Option<Address>) {
+        if let Err(error) = Self::try_notify_hub(env, game, winner) {
+            panic_with_error!(env, error);
+        }
+    }
+
+    /// `notify_hub` through `try_` calls, mapping a hub that traps to
+    /// `HubCallFailed` so `resolve_many` can fail just that session.
+    fn try_notify_hub(env: &Env, game: &Game, winner: Option<Address>) -> Result<(), Error> {
+        let Some(hub) = &game.hub else {
+            return Ok(());
+        };
+        let session_id = game.hub_session_id;
+        let player1_won = winner.as_ref() == Some(&game.player1);
+
+        // The hub releases the points it locked at `start_game`.
+        let game_hub = GameHubClient::new(env, hub);
+        let reported = match winner {
+            Some(winner) if Self::is_stake_aware_settlement(env.clone()) => {
+                let (winner_stake, loser_stake) = if player1_won {
+                    (game.player1_points, game.player2_points)
+                } else {
+                    (game.player2_points, game.player1_points)
+                };
+                if game.hub_lock_id != 0 {
+                    matches!(
+                        game_hub.try_settle_with_stakes_and_lock(
+                            &session_id,
+                            &winner,
+                            &winner_stake,
+                            &loser_stake,
+                            &game.hub_lock_id
+                        ),
+                        Ok(Ok(()))
+                    )
+                } else {
+                    matches!(
+                        game_hub.try_settle_with_stakes(
+                            &session_id,
+                            &winner,
+                            &winner_stake,
+                            &loser_stake
+                        ),
+                        Ok(Ok(()))
+                    )
+                }
+            }
+            _ if game.hub_lock_id != 0 => matches!(
+                game_hub.try_end_game_with_outcome_and_lock(
+                    &session_id,
+                    &Self::hub_outcome(game, &winner),
+                    &game.hub_lock_id
+                ),
+                Ok(Ok(()))
+            ),
+            _ => matches!(
+                game_hub.try_end_game_with_outcome(&session_id, &Self::hub_outcome(game, &winner)),
+                Ok(Ok(()))
+            ),
+        };
+        if reported {
+            Ok(())
+        } else {
+            Err(Error::HubCallFailed)
+        }
+    }
+
+    /// The `end_game_with_outcome` code for `game` reported with `winner`:
+    /// 0/1 for a player 1/2 win, 2 for a tie, 3 when nobody won.
+    fn hub_outcome(game: &Game, winner: &Option<Address>) -> u32 {
+        if Self::game_outcome(game) == Outcome::BothFoundTreasure {
+            return 2;
+        }
+        match winner {
+            Some(winner) if *winner == game.player1 => 0,
+            Some(_) => 1,
+            None => 3,
+        }
+    }
+
+    /// Settle a game that was awaiting its winner's claim.
+    fn settle_claim(env: &Env, session_id: u32, mut game: Game, winner: Address) {
+        game.awaiting_claim = false;
+        env.storage()
             .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+            .set(&DataKey::Game(session_id), &game);
+        Self::notify_hub(env, &game, Some(winner));
+    }
 
-        // Idempotent: recompute from stored energy values without re-calling GameHub.
-        if game.resolved {
-            return Ok(Self::compute_outcome(
-                game.player1_energy,
-                game.player2_energy,
-            ));
+    /// Byte encoding of an outcome, as documented under Bridge Event.
+    fn outcome_code(outcome: &Outcome) -> u8 {
+        match outcome {
+            Outcome::Player1Won => 0,
+            Outcome::Player2Won => 1,
+            Outcome::BothFoundTreasure => 2,
+            Outcome::NeitherFound => 3,
         }
+    }
 
-        // Need at least one verified player before resolving.
-        if game.player1_energy.is_none() && game.player2_energy.is_none() {
-            return Err(Error::NeitherPlayerSubmitted);
+    /// Pack a resolution into the 70-byte layout documented under Bridge
+    /// Event.
+    fn bridge_payload(
+        env: &Env,
+        session_id: u32,
+        game: &Game,
+        outcome: &Outcome,
+        player1_won: bool,
+    ) -> Bytes {
+        let mut payload = Bytes::from_array(env, &session_id.to_le_bytes());
+        payload.push_back(Self::outcome_code(outcome));
+        for player in [&game.player1, &game.player2] {
+            let digest: BytesN<32> = env
+                .crypto()
+                .keccak256(&player.to_string().to_bytes())
+                .into();
+            payload.append(&digest.into());
         }
+        payload.push_back(player1_won as u8);
+        payload
+    }
 
-        let outcome = Self::compute_outcome(game.player1_energy, game.player2_energy);
-        let player1_won = matches!(outcome, Outcome::Player1Won | Outcome::BothFoundTreasure);
+    /// The player reported to the hub as winner: nobody on an energy tie
+    /// (a draw) or when neither player found the treasure.
+    fn reported_winner(game: &Game, outcome: &Outcome) -> Option<Address> {
+        match outcome {
+            Outcome::Player1Won => Some(game.player1.clone()),
+            Outcome::Player2Won => Some(game.player2.clone()),
+            Outcome::BothFoundTreasure | Outcome::NeitherFound => None,
+        }
+    }
 
-        game.resolved = true;
-        env.storage().temporary().set(&key, &game);
+    fn record_cancel_reason(env: &Env, session_id: u32, reason: &Option<Symbol>) {
+        if let Some(reason) = reason {
+            let key = DataKey::CancelReason(session_id);
+            env.storage().temporary().set(&key, reason);
+            let ttl = Self::get_game_ttl(env.clone());
+            env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        }
+    }
 
-        // Notify Game Hub — maintains mandatory mock-game-hub integration.
-        let game_hub_addr: Address = env
+    /// Require authorization from the stored admin.
+    fn require_admin(env: &Env) {
+        let admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-        game_hub.end_game(&session_id, &player1_won);
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+    }
 
-        Ok(outcome)
+    /// `floor(amount × bps / BPS_DENOMINATOR)` for non-negative inputs,
+    /// without overflowing on large amounts.
+    fn bps_of_floor(amount: i128, bps: i128) -> i128 {
+        amount / BPS_DENOMINATOR * bps + amount % BPS_DENOMINATOR * bps / BPS_DENOMINATOR
     }
 
-    // ========================================================================
-    // Queries
-    // ========================================================================
+    /// Order-independent `ActivePair` key for two players.
+    fn pair_key(a: &Address, b: &Address) -> DataKey {
+        if a <= b {
+            DataKey::ActivePair(a.clone(), b.clone())
+        } else {
+            DataKey::ActivePair(b.clone(), a.clone())
+        }
+    }
 
-    /// Retrieve full game state for a session.
-    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
+    /// Arguments a player's `start_game` authorization is bound to.
+    fn start_auth_args(env: &Env, session_id: u32, points: i128) -> Vec<Val> {
+        vec![env, session_id.into_val(env), points.into_val(env)]
+    }
+
+    fn match_queue(env: &Env) -> Vec<QueueEntry> {
         env.storage()
-            .temporary()
-            .get(&DataKey::Game(session_id))
-            .ok_or(Error::GameNotFound)
+            .persistent()
+            .get(&DataKey::MatchQueue)
+            .unwrap_or(Vec::new(env))
     }
 
-    /// Return the treasure hash (public input) for a session.
-    ///
-    /// Frontends should use this as the `xy_nullifier_hashed` circuit input.
-    pub fn get_treasure_hash(env: Env, session_id: u32) -> Result<BytesN<32>, Error> {
-        let game: Game = env
-            .storage()
+    /// Discard a live game's proofs, mark it resolved as `NeitherFound` and
+    /// ask the hub to release both players' points.
+    fn refund(env: &Env, session_id: u32, mut game: Game) {
+        let verified = game.player1_energy.is_some() as u32 + game.player2_energy.is_some() as u32;
+        game.player1_energy = None;
+        game.player2_energy = None;
+        game.resolved = true;
+        game.resolved_ledger = Some(env.ledger().sequence());
+        game.resolved_at = Some(env.ledger().timestamp());
+        env.storage()
             .temporary()
-            .get(&DataKey::Game(session_id))
-            .ok_or(Error::GameNotFound)?;
-        Ok(game.treasure_hash)
+            .set(&DataKey::Game(session_id), &game);
+        Self::retire_live_game(env, session_id, &game, verified);
+
+        let Some(hub) = &game.hub else {
+            return;
+        };
+        let game_hub = GameHubClient::new(env, hub);
+        if game.hub_lock_id != 0 {
+            game_hub.refund_game_with_lock(&game.hub_session_id, &game.hub_lock_id);
+        } else {
+            game_hub.refund_game(&game.hub_session_id);
+        }
     }
 
-    // ========================================================================
-    // Admin Functions
-    // ========================================================================
+    /// The verifier checking `game`'s proofs: its override, else its named
+    /// verifier, else the default one.
+    fn verifier_for(env: &Env, game: &Game) -> Address {
+        if let Some(verifier) = &game.verifier_override {
+            return verifier.clone();
+        }
+        match &game.verifier {
+            Some(name) => Self::named_verifiers(env)
+                .get(name.clone())
+                .expect("Verifier not registered"),
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::VerifierAddress)
+                .expect("Verifier not set"),
+        }
+    }
 
-    pub fn get_admin(env: Env) -> Address {
+    fn deadline_passed(env: &Env, game: &Game) -> bool {
+        game.deadline_ledger
+            .is_some_and(|deadline| env.ledger().sequence() > deadline)
+    }
+
+    fn within_verifier_budget(env: &Env, proof: &Bytes, public_inputs: &Bytes) -> bool {
+        let size = proof.len().saturating_add(public_inputs.len());
+        Self::get_max_verifier_input_budget(env.clone()).is_none_or(|budget| size <= budget)
+    }
+
+    fn named_verifiers(env: &Env) -> Map<Symbol, Address> {
         env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+            .get(&DataKey::NamedVerifiers)
+            .unwrap_or(Map::new(env))
     }
 
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// Adjust the live-game count of the verifier registered under `name`.
+    fn update_verifier_games(env: &Env, name: &Symbol, delta: i32) {
+        let key = DataKey::VerifierGames(name.clone());
+        let live: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let live = live.saturating_add_signed(delta);
+        if live == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &live);
+        }
     }
 
-    pub fn get_hub(env: Env) -> Address {
+    fn category_hubs(env: &Env) -> Map<Symbol, Address> {
         env.storage()
             .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set")
+            .get(&DataKey::CategoryHubs)
+            .unwrap_or(Map::new(env))
     }
 
-    pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env
+    /// Append a resolution to the player's results, dropping the oldest
+    /// entry when the buffer is full.
+    fn record_player_result(env: &Env, player: &Address, session_id: u32, won: bool) {
+        let key = DataKey::PlayerResults(player.clone());
+        let mut results: Vec<(u32, bool)> = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if results.len() >= MAX_PLAYER_RESULTS {
+            results.pop_front();
+        }
+        results.push_back((session_id, won));
+        env.storage().persistent().set(&key, &results);
+    }
+
+    /// Count a win (`Greater`), loss (`Less`) or draw (`Equal`) for `player`.
+    fn record_player_stats(env: &Env, player: &Address, result: Ordering) {
+        let mut stats = Self::get_stats(env.clone(), player.clone());
+        let tally = match result {
+            Ordering::Greater => &mut stats.wins,
+            Ordering::Less => &mut stats.losses,
+            Ordering::Equal => &mut stats.draws,
+        };
+        *tally = tally.saturating_add(1);
         env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &new_hub);
+            .persistent()
+            .set(&DataKey::Stats(player.clone()), &stats);
     }
 
-    pub fn get_verifier(env: Env) -> Address {
+    /// Count `outcome` in the current ledger's outcome bucket.
+    fn record_outcome_stats(env: &Env, outcome: &Outcome) {
+        let key = DataKey::OutcomeBucket(env.ledger().sequence() / OUTCOME_BUCKET_LEDGERS);
+        let mut stats: OutcomeStats = env.storage().persistent().get(&key).unwrap_or_default();
+        let tally = match outcome {
+            Outcome::Player1Won => &mut stats.player1_won,
+            Outcome::Player2Won => &mut stats.player2_won,
+            Outcome::BothFoundTreasure => &mut stats.both_found,
+            Outcome::NeitherFound => &mut stats.neither_found,
+        };
+        *tally = tally.saturating_add(1);
+        env.storage().persistent().set(&key, &stats);
+    }
+
+    /// Whether `address` is a deployed Wasm contract (not an account, a
+    /// Stellar asset contract, or nothing at all).
+    fn is_wasm_contract(address: &Address) -> bool {
+        matches!(address.executable(), Some(Executable::Wasm(_)))
+    }
+
+    fn game_phase(game: &Game) -> GamePhase {
+        if game.resolved {
+            return GamePhase::Resolved;
+        }
+        match (game.player1_energy, game.player2_energy) {
+            (None, None) => GamePhase::Open,
+            (Some(_), Some(_)) => GamePhase::BothVerified,
+            _ => GamePhase::OneVerified,
+        }
+    }
+
+    fn update_phase_counts(env: &Env, update: impl FnOnce(&mut PhaseCounts)) {
+        let mut counts = Self::phase_counts(env.clone());
+        update(&mut counts);
+        env.storage().instance().set(&DataKey::PhaseCounts, &counts);
+    }
+
+    fn check_points(points: i128) -> Result<(), Error> {
+        if points <= 0 || points > MAX_POINTS_CEILING {
+            return Err(Error::InvalidPoints);
+        }
+        Ok(())
+    }
+
+    fn check_points_cap(max: i128) -> Result<(), Error> {
+        if max <= 0 || max > MAX_POINTS_CEILING {
+            return Err(Error::InvalidPointsCap);
+        }
+        Ok(())
+    }
+
+    /// The points cap and eligibility checks `start_session` will apply to
+    /// a queued `player` matched into `session_id`.
+    fn check_queue_entry(
+        env: &Env,
+        session_id: u32,
+        player: &Address,
+        points: i128,
+    ) -> Result<(), Error> {
+        if Self::points_cap_for(env, session_id).is_some_and(|cap| points > cap) {
+            return Err(Error::PointsAboveCap);
+        }
+        if Self::is_eligibility_required(env.clone())
+            && !Self::is_eligible(env.clone(), player.clone())
+        {
+            return Err(Error::NotEligible);
+        }
+        Ok(())
+    }
+
+    /// Points cap for `session_id`: its per-game grant, else the global cap.
+    fn points_cap_for(env: &Env, session_id: u32) -> Option<i128> {
         env.storage()
-            .instance()
-            .get(&DataKey::VerifierAddress)
-            .expect("Verifier not set")
+            .temporary()
+            .get(&DataKey::GameMaxPoints(session_id))
+            .or_else(|| Self::get_max_points(env.clone()))
     }
 
-    /// Update the verifier contract address.
+    /// TTL a submission extends its game to.
     ///
-    /// ⚠ Verifier Upgrade Warning: if the new verifier embeds a different VK,
-    /// all proofs generated against the old VK will fail.  Coordinate upgrades
-    /// carefully with all active players.
-    pub fn set_verifier(env: Env, new_verifier: Address) {
-        let admin: Address = env
-            .storage()
+    /// `get_game_ttl` by default.  With `ProportionalTtl` on, the
+    /// extension scales with the budget left after this submission,
+    /// `get_game_ttl × remaining / max_submissions`, floored at
+    /// `MIN_SUBMISSION_TTL_LEDGERS` and capped at the network's maximum entry
+    /// TTL.
+    fn submission_ttl(env: &Env, game: &Game) -> u32 {
+        let ttl = Self::get_game_ttl(env.clone());
+        if !Self::is_proportional_ttl(env.clone()) {
+            return ttl;
+        }
+        let remaining = game.max_submissions.saturating_sub(game.submissions) as u64;
+        let scaled = ttl as u64 * remaining / game.max_submissions.max(1) as u64;
+        (scaled as u32)
+            .max(MIN_SUBMISSION_TTL_LEDGERS)
+            .min(env.storage().max_ttl())
+    }
+
+    fn active_session_at(env: &Env, index: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ActiveSessionAt(index))
+            .expect("Active-session index is inconsistent")
+    }
+
+    /// Append `session_id` to the active-session index.
+    fn push_active_session(env: &Env, session_id: u32) {
+        let count = Self::active_session_count(env.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveSessionAt(count), &session_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveSessionSlot(session_id), &count);
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+            .set(&DataKey::ActiveSessionCount, &(count + 1));
+    }
+
+    /// Remove `session_id` from the active-session index, moving the last
+    /// entry into its position.  Returns false if it was not listed.
+    fn remove_active_session(env: &Env, session_id: u32) -> bool {
+        let slot_key = DataKey::ActiveSessionSlot(session_id);
+        let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return false;
+        };
+        let last = Self::active_session_count(env.clone()) - 1;
+        if slot != last {
+            let moved = Self::active_session_at(env, last);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ActiveSessionAt(slot), &moved);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ActiveSessionSlot(moved), &slot);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ActiveSessionAt(last));
+        env.storage().persistent().remove(&slot_key);
         env.storage()
             .instance()
-            .set(&DataKey::VerifierAddress, &new_verifier);
+            .set(&DataKey::ActiveSessionCount, &last);
+        true
     }
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env
+    /// Session bound to `name`, unless its game has since expired or been
+    /// deleted.
+    fn named_session(env: &Env, name: &Bytes) -> Option<u32> {
+        let session_id = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SessionName(name.clone()))?;
+        let game = Self::load_game(env, session_id).ok()?;
+        (game.name.as_ref() == Some(name)).then_some(session_id)
+    }
+
+    fn update_session_name_count(env: &Env, update: impl FnOnce(u32) -> u32) {
+        let count = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+            .get(&DataKey::SessionNameCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::SessionNameCount, &update(count));
     }
 
-    // ========================================================================
-    // Private Helpers
-    // ========================================================================
+    /// Resolve the Game Hub for a session's category, falling back to the
+    /// default hub for absent or unregistered categories.  `None` when
+    /// neither exists (standalone deployment).
+    fn hub_for(env: &Env, category: &Option<Symbol>) -> Option<Address> {
+        if let Some(category) = category {
+            if let Some(hub) = Self::category_hubs(env).get(category.clone()) {
+                return Some(hub);
+            }
+        }
+        env.storage().instance().get(&DataKey::GameHubAddress)
+    }
+
+    /// The arbiter's ruling if there is one, else a forfeit's winner, else
+    /// the outcome under the game's mode.
+    fn game_outcome(game: &Game) -> Outcome {
+        if game.arbitrated {
+            return game.arbitrated_outcome.clone();
+        }
+        match &game.forfeited_by {
+            Some(player) if *player == game.player1 => return Outcome::Player2Won,
+            Some(_) => return Outcome::Player1Won,
+            None => {}
+        }
+        if game.mode == GameMode::EarliestVerified
+            && game.player1_energy.is_some()
+            && game.player2_energy.is_some()
+        {
+            // A missing time (a hypothetical proof) counts as the later one.
+            let first = game.player1_verified_ledger.zip(game.player1_verified_at);
+            let second = game.player2_verified_ledger.zip(game.player2_verified_at);
+            let order = match (first, second) {
+                (Some(t1), Some(t2)) => t1.cmp(&t2),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            match order {
+                Ordering::Less => return Outcome::Player1Won,
+                Ordering::Greater => return Outcome::Player2Won,
+                Ordering::Equal => {}
+            }
+        }
+        Self::compute_outcome(game.player1_energy, game.player2_energy)
+    }
 
     /// Determine the outcome from energy values.
     ///
@@ -541,7 +5591,7 @@ impl EatherGridContract {
     /// - Only p2 submitted → `Player2Won`.
     /// - Both submitted, e1 < e2  → `Player1Won`.
     /// - Both submitted, e2 < e1  → `Player2Won`.
-    /// - Both submitted, e1 == e2 → `BothFoundTreasure` (tie, GameHub gets player1_won = true).
+    /// - Both submitted, e1 == e2 → `BothFoundTreasure` (tie, a draw).
     /// - Neither submitted        → `NeitherFound` (should be unreachable from resolve_game).
     fn compute_outcome(p1_energy: Option<u32>, p2_energy: Option<u32>) -> Outcome {
         match (p1_energy, p2_energy) {
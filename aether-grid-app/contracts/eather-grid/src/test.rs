@@ -9,9 +9,27 @@
 //! The `energy_used` field is caller-supplied and therefore fully controllable
 //! in these tests without needing a real Noir prover.
 
-use crate::{EatherGridContract, EatherGridContractClient, Error, Outcome};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+
+use crate::{
+    AuthRequirement, EatherGridContract, EatherGridContractClient, Error, FeeConfig, GameMode,
+    GameOptions, GamePhase, LateSubmitPolicy, LobbyEntry, Outcome, OutcomeStats, PhaseCounts,
+    PlayerStats, PointsSplit, RoundingPolicy, StateProof, StorageReport, TieDefault, VerifierMode,
+    VerifierStats, DERIVATION_V0, DERIVATION_V1, DOMAIN_TAG, MAX_CURRENCY_DECIMALS,
+    MAX_POINTS_CEILING, MAX_REJECTED_INPUTS_LEN, MAX_SESSION_NAME_LEN, MAX_STATS_BUCKETS,
+    OUTCOME_BUCKET_LEDGERS, QUEUE_SESSION_BASE, VERSION,
+};
+use soroban_sdk::testutils::{
+    storage::Temporary as _, Address as _, AuthorizedFunction, AuthorizedInvocation, Events as _,
+    Ledger as _, MockAuth, MockAuthInvoke, MuxedAddress as _,
+};
+use soroban_sdk::xdr::{ContractEventBody, ScVal};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, IntoVal, MuxedAddress,
+    Symbol, TryFromVal, Val, Vec,
+};
 
 // ============================================================================
 // Mock Contracts
@@ -36,9 +54,217 @@ impl MockGameHub {
     pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
         // no-op
     }
+    pub fn end_game_with_outcome(env: Env, _session_id: u32, outcome: u32) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("outcome"), &outcome);
+    }
+    pub fn last_outcome(env: Env) -> Option<u32> {
+        env.storage().instance().get(&symbol_short!("outcome"))
+    }
     pub fn add_game(_env: Env, _game_address: Address) {
         // no-op
     }
+    pub fn settle_with_stakes(
+        _env: Env,
+        _session_id: u32,
+        _winner: Address,
+        _winner_stake: i128,
+        _loser_stake: i128,
+    ) {
+        // no-op
+    }
+}
+
+/// Hub that records the sessions it has started and ended, for routing and
+/// settlement assertions.
+#[contract]
+pub struct RecordingHub;
+
+#[contractimpl]
+impl RecordingHub {
+    pub fn start_game(
+        env: Env,
+        _game_id: Address,
+        session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+        let mut started = Self::started(env.clone());
+        started.push_back(session_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("started"), &started);
+    }
+    /// Records `(session_id, outcome)` in `ended`.
+    pub fn end_game_with_outcome(env: Env, session_id: u32, outcome: u32) {
+        let mut ended = Self::ended(env.clone());
+        ended.push_back((session_id, outcome));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ended"), &ended);
+    }
+    pub fn settle_with_stakes(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        winner_stake: i128,
+        loser_stake: i128,
+    ) {
+        let mut settled = Self::settled(env.clone());
+        settled.push_back((session_id, winner, winner_stake, loser_stake));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("settled"), &settled);
+    }
+    pub fn refund_game(env: Env, session_id: u32) {
+        let mut refunded = Self::refunded(env.clone());
+        refunded.push_back(session_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("refunded"), &refunded);
+    }
+    pub fn cancel_game(env: Env, session_id: u32) {
+        let mut cancelled = Self::cancelled(env.clone());
+        cancelled.push_back(session_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("cancelled"), &cancelled);
+    }
+    pub fn started(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("started"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn ended(env: Env) -> Vec<(u32, u32)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ended"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn settled(env: Env) -> Vec<(u32, Address, i128, i128)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("settled"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn refunded(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("refunded"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn cancelled(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("cancelled"))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+/// Hub that accepts sessions but traps when asked to settle one.
+#[contract]
+pub struct TrappingHub;
+
+#[contractimpl]
+impl TrappingHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+    pub fn end_game_with_outcome(_env: Env, _session_id: u32, _outcome: u32) {
+        panic!("hub is down");
+    }
+}
+
+/// Hub that hands out a lock id from `start_game` and records the lock ids
+/// it gets back at settlement.
+#[contract]
+pub struct LockingHub;
+
+#[contractimpl]
+impl LockingHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) -> u64 {
+        7_000 + session_id as u64
+    }
+    pub fn end_game_with_outcome(_env: Env, _session_id: u32, _outcome: u32) {
+        panic!("lock-aware sessions must settle via end_game_with_outcome_and_lock");
+    }
+    pub fn end_game_with_outcome_and_lock(env: Env, session_id: u32, outcome: u32, lock_id: u64) {
+        let mut ended = Self::ended(env.clone());
+        ended.push_back((session_id, outcome, lock_id));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ended"), &ended);
+    }
+    pub fn ended(env: Env) -> Vec<(u32, u32, u64)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ended"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn settle_with_stakes(
+        _env: Env,
+        _session_id: u32,
+        _winner: Address,
+        _winner_stake: i128,
+        _loser_stake: i128,
+    ) {
+        panic!("lock-aware sessions must settle via settle_with_stakes_and_lock");
+    }
+    pub fn settle_with_stakes_and_lock(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        winner_stake: i128,
+        loser_stake: i128,
+        lock_id: u64,
+    ) {
+        let mut settled = Self::settled(env.clone());
+        settled.push_back((session_id, winner, winner_stake, loser_stake, lock_id));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("settled"), &settled);
+    }
+    pub fn settled(env: Env) -> Vec<(u32, Address, i128, i128, u64)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("settled"))
+            .unwrap_or(Vec::new(&env))
+    }
+    pub fn refund_game(_env: Env, _session_id: u32) {
+        panic!("lock-aware sessions must refund via refund_game_with_lock");
+    }
+    pub fn refund_game_with_lock(env: Env, session_id: u32, lock_id: u64) {
+        let mut refunded = Self::refunded(env.clone());
+        refunded.push_back((session_id, lock_id));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("refunded"), &refunded);
+    }
+    pub fn refunded(env: Env) -> Vec<(u32, u64)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("refunded"))
+            .unwrap_or(Vec::new(&env))
+    }
 }
 
 /// Mock verifier: traps if proof is empty or starts with 0xff; passes otherwise.
@@ -47,7 +273,7 @@ pub struct MockVerifier;
 
 #[contractimpl]
 impl MockVerifier {
-    pub fn verify_proof(_env: Env, proof: Bytes, _public_inputs: Bytes) {
+    pub fn verify_proof(_env: Env, _public_inputs: Bytes, proof: Bytes) {
         if proof.is_empty() {
             panic!("verify_proof: empty proof");
         }
@@ -58,6 +284,52 @@ impl MockVerifier {
     }
 }
 
+/// Mock verifier that returns `false` for proofs starting with 0xff instead
+/// of trapping.
+#[contract]
+pub struct MockBoolVerifier;
+
+#[contractimpl]
+impl MockBoolVerifier {
+    pub fn verify_proof(_env: Env, _public_inputs: Bytes, proof: Bytes) -> bool {
+        !proof.is_empty() && proof.get(0) != Some(0xff)
+    }
+}
+
+/// Mock verifier for a circuit no test proof satisfies: always traps.
+#[contract]
+pub struct MockRejectingVerifier;
+
+#[contractimpl]
+impl MockRejectingVerifier {
+    pub fn verify_proof(_env: Env, _public_inputs: Bytes, _proof: Bytes) {
+        panic!("verify_proof: rejected by pinned verifier");
+    }
+}
+
+/// Mock verifier exposing `is_paused`, reporting the `paused` flag stored
+/// by `set_paused`.
+#[contract]
+pub struct MockPausableVerifier;
+
+#[contractimpl]
+impl MockPausableVerifier {
+    pub fn verify_proof(_env: Env, _public_inputs: Bytes, _proof: Bytes) {}
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("paused"))
+            .unwrap_or(false)
+    }
+
+    pub fn set_paused(env: Env, paused: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("paused"), &paused);
+    }
+}
+
 // ============================================================================
 // Test Setup
 // ============================================================================
@@ -91,6 +363,11 @@ fn invalid_proof(env: &Env) -> Bytes {
 }
 
 fn setup() -> TestSetup {
+    setup_with_hub(true)
+}
+
+/// Like `setup`, but a standalone deployment when `with_hub` is false.
+fn setup_with_hub(with_hub: bool) -> TestSetup {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -106,9 +383,9 @@ fn setup() -> TestSetup {
     });
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = with_hub.then(|| env.register(MockGameHub, ()));
     let verifier_addr = env.register(MockVerifier, ());
-    let contract_id = env.register(EatherGridContract, (&admin, &hub_addr, &verifier_addr));
+    let contract_id = env.register(EatherGridContract, (&admin, hub_addr, &verifier_addr));
     let client = EatherGridContractClient::new(&env, &contract_id);
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
@@ -122,7 +399,7 @@ fn setup() -> TestSetup {
     }
 }
 
-const POINTS: i128 = 100_0000_000;
+const POINTS: i128 = 1_000_000_000;
 
 /// Start a standard game; returns the treasure hash used.
 fn start(ts: &TestSetup, session_id: u32) -> BytesN<32> {
@@ -138,6 +415,21 @@ fn start(ts: &TestSetup, session_id: u32) -> BytesN<32> {
     hash
 }
 
+/// Start a game with the given options; returns the treasure hash used.
+fn start_with(ts: &TestSetup, session_id: u32, options: &GameOptions) -> BytesN<32> {
+    let hash = test_treasure_hash(&ts.env);
+    ts.client.start_game_with_options(
+        &session_id,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+        options,
+    );
+    hash
+}
+
 /// Helper to assert an error variant from a try_* call.
 fn assert_error<T, E>(
     result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
@@ -445,11 +737,100 @@ fn test_verifier_stored_and_queryable() {
 #[test]
 fn test_admin_can_update_verifier() {
     let ts = setup();
-    let new_ver = Address::generate(&ts.env);
+    let new_ver = ts.env.register(MockVerifier, ());
     ts.client.set_verifier(&new_ver);
     assert_eq!(ts.client.get_verifier(), new_ver);
 }
 
+#[test]
+fn test_is_admin() {
+    let ts = setup();
+    assert!(ts.client.is_admin(&ts.client.get_admin()));
+    assert!(!ts.client.is_admin(&ts.player1));
+}
+
+/// Mock exactly one auth: `address` authorizing `fn_name(args)` on the
+/// contract.
+fn mock_single_auth(ts: &TestSetup, address: &Address, fn_name: &str, args: Vec<Val>) {
+    ts.env.mock_auths(&[MockAuth {
+        address,
+        invoke: &MockAuthInvoke {
+            contract: &ts.client.address,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_admin_handoff_propose_accept() {
+    let ts = setup();
+    let old_admin = ts.client.get_admin();
+    let new_admin = Address::generate(&ts.env);
+    assert_eq!(ts.client.get_pending_admin(), None);
+
+    ts.client.propose_admin(&new_admin);
+    assert_eq!(ts.client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(ts.client.get_admin(), old_admin);
+
+    ts.client.accept_admin();
+    assert_eq!(ts.env.auths()[0].0, new_admin);
+    assert_eq!(ts.client.get_admin(), new_admin);
+    assert_eq!(ts.client.get_pending_admin(), None);
+    assert_error(&ts.client.try_accept_admin(), Error::NoPendingAdmin);
+}
+
+#[test]
+fn test_accept_admin_rejects_non_pending_address() {
+    let ts = setup();
+    let typo = Address::generate(&ts.env);
+    let intended = Address::generate(&ts.env);
+    ts.client.propose_admin(&typo);
+    // Proposing again replaces the pending proposal.
+    ts.client.propose_admin(&intended);
+
+    mock_single_auth(&ts, &typo, "accept_admin", Vec::new(&ts.env));
+    assert!(ts.client.try_accept_admin().is_err());
+    assert_eq!(ts.client.get_pending_admin(), Some(intended));
+}
+
+#[test]
+fn test_old_admin_keeps_control_until_acceptance() {
+    let ts = setup();
+    let old_admin = ts.client.get_admin();
+    let new_admin = Address::generate(&ts.env);
+    ts.client.propose_admin(&new_admin);
+
+    mock_single_auth(
+        &ts,
+        &old_admin,
+        "set_max_submissions",
+        (3u32,).into_val(&ts.env),
+    );
+    ts.client.set_max_submissions(&3u32);
+    assert_eq!(ts.client.get_max_submissions(), 3);
+
+    mock_single_auth(&ts, &new_admin, "accept_admin", Vec::new(&ts.env));
+    ts.client.accept_admin();
+
+    mock_single_auth(
+        &ts,
+        &old_admin,
+        "set_max_submissions",
+        (4u32,).into_val(&ts.env),
+    );
+    assert!(ts.client.try_set_max_submissions(&4u32).is_err());
+    mock_single_auth(
+        &ts,
+        &new_admin,
+        "set_max_submissions",
+        (4u32,).into_val(&ts.env),
+    );
+    ts.client.set_max_submissions(&4u32);
+    assert_eq!(ts.client.get_max_submissions(), 4);
+}
+
 #[test]
 fn test_upgrade_function_exists() {
     let env = Env::default();
@@ -457,9 +838,5311 @@ fn test_upgrade_function_exists() {
     let admin = Address::generate(&env);
     let hub = env.register(MockGameHub, ());
     let ver = env.register(MockVerifier, ());
-    let cid = env.register(EatherGridContract, (&admin, &hub, &ver));
+    let cid = env.register(EatherGridContract, (&admin, Some(hub), &ver));
     let client = EatherGridContractClient::new(&env, &cid);
     // Upgrade will fail (no WASM with that hash) — that is expected.
     let result = client.try_upgrade(&BytesN::from_array(&env, &[1u8; 32]));
     assert!(result.is_err(), "upgrade with non-existent WASM must error");
 }
+
+#[test]
+fn test_get_version_returns_constant() {
+    let ts = setup();
+    assert_eq!(ts.client.get_version(), VERSION);
+    assert_eq!(ts.client.get_schema_version(), VERSION);
+}
+
+#[test]
+fn test_migrate_is_admin_gated_and_idempotent() {
+    let ts = setup();
+    let outsider = Address::generate(&ts.env);
+    mock_single_auth(&ts, &outsider, "migrate", Vec::new(&ts.env));
+    assert!(ts.client.try_migrate().is_err());
+
+    // A pre-versioning instance has no schema version yet.
+    ts.env.as_contract(&ts.client.address, || {
+        ts.env
+            .storage()
+            .instance()
+            .remove(&crate::DataKey::SchemaVersion);
+    });
+    assert_eq!(ts.client.get_schema_version(), 0);
+
+    let admin = ts.client.get_admin();
+    mock_single_auth(&ts, &admin, "migrate", Vec::new(&ts.env));
+    ts.client.migrate();
+    assert_eq!(ts.client.get_schema_version(), VERSION);
+    mock_single_auth(&ts, &admin, "migrate", Vec::new(&ts.env));
+    ts.client.migrate();
+    assert_eq!(ts.client.get_schema_version(), VERSION);
+}
+
+// ============================================================================
+// Submission Budget
+// ============================================================================
+
+#[test]
+fn test_remaining_submissions_after_partial_use() {
+    let ts = setup();
+    let hash = start(&ts, 110);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    assert_eq!(ts.client.remaining_submissions(&110u32), 2);
+
+    ts.client
+        .submit_zk_proof(&110u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_eq!(ts.client.remaining_submissions(&110u32), 1);
+
+    ts.client
+        .submit_zk_proof(&110u32, &ts.player2, &valid_proof(&ts.env), &pi, &60u32);
+    assert_eq!(ts.client.remaining_submissions(&110u32), 0);
+}
+
+#[test]
+fn test_submission_cap_blocks_second_player() {
+    let ts = setup();
+    ts.client.set_max_submissions(&1u32);
+    let hash = start(&ts, 111);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+
+    ts.client
+        .submit_zk_proof(&111u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_eq!(ts.client.remaining_submissions(&111u32), 0);
+    let result =
+        ts.client
+            .try_submit_zk_proof(&111u32, &ts.player2, &valid_proof(&ts.env), &pi, &40u32);
+    assert_error(&result, Error::SubmissionBudgetExhausted);
+}
+
+#[test]
+fn test_submission_cap_is_snapshotted_per_session() {
+    let ts = setup();
+    start(&ts, 112);
+    ts.client.set_max_submissions(&1u32);
+    assert_eq!(ts.client.remaining_submissions(&112u32), 2);
+}
+
+#[test]
+fn test_remaining_submissions_unknown_session() {
+    let ts = setup();
+    let result = ts.client.try_remaining_submissions(&113u32);
+    assert_error(&result, Error::GameNotFound);
+}
+
+// ============================================================================
+// Game Categories
+// ============================================================================
+
+#[test]
+fn test_categories_route_to_their_own_hubs() {
+    let ts = setup();
+    let ranked_hub = ts.env.register(RecordingHub, ());
+    let casual_hub = ts.env.register(RecordingHub, ());
+    ts.client
+        .set_category_hub(&symbol_short!("ranked"), &ranked_hub);
+    ts.client
+        .set_category_hub(&symbol_short!("casual"), &casual_hub);
+
+    let hash = test_treasure_hash(&ts.env);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    for (session_id, category) in [
+        (120u32, symbol_short!("ranked")),
+        (121, symbol_short!("casual")),
+    ] {
+        ts.client.start_game_with_options(
+            &session_id,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &hash,
+            &GameOptions {
+                category: Some(category),
+                ..Default::default()
+            },
+        );
+    }
+    ts.client
+        .submit_zk_proof(&120u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&121u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&120u32);
+    ts.client.resolve_game(&121u32);
+
+    let ranked = RecordingHubClient::new(&ts.env, &ranked_hub);
+    let casual = RecordingHubClient::new(&ts.env, &casual_hub);
+    assert_eq!(ranked.started(), Vec::from_array(&ts.env, [120u32]));
+    assert_eq!(ranked.ended(), Vec::from_array(&ts.env, [(120u32, 0u32)]));
+    assert_eq!(casual.started(), Vec::from_array(&ts.env, [121u32]));
+    assert_eq!(casual.ended(), Vec::from_array(&ts.env, [(121u32, 1u32)]));
+}
+
+#[test]
+fn test_unknown_category_falls_back_to_default_hub() {
+    let ts = setup();
+    let ranked_hub = ts.env.register(RecordingHub, ());
+    ts.client
+        .set_category_hub(&symbol_short!("ranked"), &ranked_hub);
+
+    let hash = test_treasure_hash(&ts.env);
+    ts.client.start_game_with_options(
+        &122u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+        &GameOptions {
+            category: Some(symbol_short!("unknown")),
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&122u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&122u32);
+
+    let ranked = RecordingHubClient::new(&ts.env, &ranked_hub);
+    assert!(ranked.started().is_empty());
+    assert!(ranked.ended().is_empty());
+    assert_eq!(
+        ts.client.get_game(&122u32).category,
+        Some(symbol_short!("unknown"))
+    );
+}
+
+#[test]
+fn test_remove_category_hub() {
+    let ts = setup();
+    let hub = ts.env.register(RecordingHub, ());
+    ts.client.set_category_hub(&symbol_short!("ranked"), &hub);
+    assert_eq!(
+        ts.client.get_category_hub(&symbol_short!("ranked")),
+        Some(hub)
+    );
+    ts.client.remove_category_hub(&symbol_short!("ranked"));
+    assert_eq!(ts.client.get_category_hub(&symbol_short!("ranked")), None);
+}
+
+// ============================================================================
+// Resolution Views
+// ============================================================================
+
+#[test]
+fn test_resolution_views_on_live_game_return_not_resolved() {
+    let ts = setup();
+    let hash = start(&ts, 130);
+    assert_error(&ts.client.try_get_outcome(&130u32), Error::GameNotResolved);
+    assert_error(&ts.client.try_get_winner(&130u32), Error::GameNotResolved);
+
+    // Still unresolved after a submission.
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&130u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_error(&ts.client.try_get_outcome(&130u32), Error::GameNotResolved);
+    assert_error(&ts.client.try_get_winner(&130u32), Error::GameNotResolved);
+}
+
+#[test]
+fn test_resolution_views_on_missing_game_return_not_found() {
+    let ts = setup();
+    assert_error(&ts.client.try_get_outcome(&131u32), Error::GameNotFound);
+    assert_error(&ts.client.try_get_winner(&131u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_resolution_views_after_resolve() {
+    let ts = setup();
+    let hash = start(&ts, 132);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&132u32, &ts.player2, &valid_proof(&ts.env), &pi, &50u32);
+    ts.client.resolve_game(&132u32);
+    assert_eq!(ts.client.get_outcome(&132u32), Outcome::Player2Won);
+    assert_eq!(ts.client.get_winner(&132u32), Some(ts.player2.clone()));
+}
+
+// ============================================================================
+// Force Resolution
+// ============================================================================
+
+#[test]
+fn test_force_resolve_records_reason() {
+    let ts = setup();
+    start(&ts, 140);
+    let outcome = ts
+        .client
+        .force_resolve(&140u32, &Some(symbol_short!("stuck")));
+    assert_eq!(outcome, Outcome::NeitherFound);
+    assert!(ts.client.get_game(&140u32).resolved);
+    assert_eq!(
+        ts.client.get_cancel_reason(&140u32),
+        Some(symbol_short!("stuck"))
+    );
+}
+
+#[test]
+fn test_force_resolve_without_reason() {
+    let ts = setup();
+    let hash = start(&ts, 141);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&141u32, &ts.player2, &valid_proof(&ts.env), &pi, &50u32);
+    assert_eq!(ts.client.force_resolve(&141u32, &None), Outcome::Player2Won);
+    assert_eq!(ts.client.get_cancel_reason(&141u32), None);
+}
+
+#[test]
+fn test_force_resolve_rejects_resolved_game() {
+    let ts = setup();
+    let hash = start(&ts, 142);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&142u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    ts.client.resolve_game(&142u32);
+    let result = ts
+        .client
+        .try_force_resolve(&142u32, &Some(symbol_short!("late")));
+    assert_error(&result, Error::GameAlreadyResolved);
+}
+
+// ============================================================================
+// Time Limit
+// ============================================================================
+
+#[test]
+fn test_submission_within_time_limit_accepted() {
+    let ts = setup();
+    let hash = start_with(
+        &ts,
+        150,
+        &GameOptions {
+            time_limit_seconds: Some(600),
+            ..Default::default()
+        },
+    );
+    assert_eq!(ts.client.get_game(&150u32).started_at, 1_700_000_000);
+    ts.env.ledger().with_mut(|li| li.timestamp += 600);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&150u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+}
+
+#[test]
+fn test_submission_after_time_limit_rejected() {
+    let ts = setup();
+    let hash = start_with(
+        &ts,
+        151,
+        &GameOptions {
+            time_limit_seconds: Some(600),
+            ..Default::default()
+        },
+    );
+    ts.env.ledger().with_mut(|li| li.timestamp += 601);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let result =
+        ts.client
+            .try_submit_zk_proof(&151u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_error(&result, Error::TimeLimitExceeded);
+}
+
+#[test]
+fn test_no_time_limit_by_default() {
+    let ts = setup();
+    let hash = start(&ts, 152);
+    ts.env
+        .ledger()
+        .with_mut(|li| li.timestamp += 30 * 24 * 3600);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&152u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+}
+
+// ============================================================================
+// Submission Preflight
+// ============================================================================
+
+#[test]
+fn test_would_accept_submission_ok_without_side_effects() {
+    let ts = setup();
+    let hash = start(&ts, 160);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let before = ts.client.get_game(&160u32);
+    ts.client.would_accept_submission(&160u32, &ts.player1, &pi);
+    assert_eq!(ts.client.get_game(&160u32), before);
+}
+
+#[test]
+fn test_would_accept_submission_rejections() {
+    let ts = setup();
+    let hash = start(&ts, 161);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let wrong_pi = Bytes::from_array(&ts.env, &[0x00u8; 32]);
+    let outsider = Address::generate(&ts.env);
+
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&999u32, &ts.player1, &pi),
+        Error::GameNotFound,
+    );
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&161u32, &outsider, &pi),
+        Error::NotPlayer,
+    );
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&161u32, &ts.player1, &wrong_pi),
+        Error::PublicInputMismatch,
+    );
+
+    ts.client
+        .submit_zk_proof(&161u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&161u32, &ts.player1, &pi),
+        Error::AlreadySubmitted,
+    );
+
+    ts.client.resolve_game(&161u32);
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&161u32, &ts.player2, &pi),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_would_accept_submission_budget_and_time_limit() {
+    let ts = setup();
+    ts.client.set_max_submissions(&1u32);
+    let hash = start_with(
+        &ts,
+        162,
+        &GameOptions {
+            time_limit_seconds: Some(60),
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&162u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&162u32, &ts.player2, &pi),
+        Error::SubmissionBudgetExhausted,
+    );
+
+    let hash = start_with(
+        &ts,
+        163,
+        &GameOptions {
+            time_limit_seconds: Some(60),
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.env.ledger().with_mut(|li| li.timestamp += 61);
+    assert_error(
+        &ts.client
+            .try_would_accept_submission(&163u32, &ts.player2, &pi),
+        Error::TimeLimitExceeded,
+    );
+}
+
+// ============================================================================
+// Eligibility
+// ============================================================================
+
+#[test]
+fn test_bulk_eligible_players_can_start() {
+    let ts = setup();
+    ts.client.set_eligibility_required(&true);
+    let p3 = Address::generate(&ts.env);
+    let players = Vec::from_array(
+        &ts.env,
+        [ts.player1.clone(), ts.player2.clone(), p3.clone()],
+    );
+    ts.client.add_eligible_many(&players);
+
+    assert!(ts.client.is_eligible(&ts.player1));
+    assert!(ts.client.is_eligible(&p3));
+    start(&ts, 170);
+    ts.client.start_game(
+        &171u32,
+        &p3,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+}
+
+#[test]
+fn test_ineligible_player_cannot_start() {
+    let ts = setup();
+    ts.client.set_eligibility_required(&true);
+    ts.client
+        .add_eligible_many(&Vec::from_array(&ts.env, [ts.player1.clone()]));
+    let result = ts.client.try_start_game(
+        &172u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+    assert_error(&result, Error::NotEligible);
+}
+
+#[test]
+fn test_bulk_remove_eligible() {
+    let ts = setup();
+    let players = Vec::from_array(&ts.env, [ts.player1.clone(), ts.player2.clone()]);
+    ts.client.add_eligible_many(&players);
+    ts.client.remove_eligible_many(&players);
+    assert!(!ts.client.is_eligible(&ts.player1));
+    assert!(!ts.client.is_eligible(&ts.player2));
+}
+
+#[test]
+fn test_eligibility_batch_is_capped() {
+    let ts = setup();
+    let mut players = Vec::new(&ts.env);
+    for _ in 0..51 {
+        players.push_back(Address::generate(&ts.env));
+    }
+    assert_error(
+        &ts.client.try_add_eligible_many(&players),
+        Error::BatchTooLarge,
+    );
+}
+
+// ============================================================================
+// Nullifier Derivation
+// ============================================================================
+
+/// Reference implementation of the nullifier preimage.
+fn expected_nullifier(
+    env: &Env,
+    tagged: bool,
+    contract: &Address,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    if tagged {
+        preimage.extend_from_slice(DOMAIN_TAG);
+    }
+    preimage.extend_from_array(&session_id.to_be_bytes());
+    preimage.append(&player1.to_string().to_bytes());
+    preimage.append(&player2.to_string().to_bytes());
+    preimage.append(&contract.to_string().to_bytes());
+    env.crypto().keccak256(&preimage).into()
+}
+
+#[test]
+fn test_untagged_nullifier_by_default() {
+    let ts = setup();
+    start(&ts, 180);
+    let game = ts.client.get_game(&180u32);
+    assert_eq!(game.derivation_version, DERIVATION_V0);
+    assert_eq!(
+        game.nullifier,
+        expected_nullifier(
+            &ts.env,
+            false,
+            &ts.client.address,
+            180,
+            &ts.player1,
+            &ts.player2
+        )
+    );
+    assert_eq!(ts.client.get_nullifier(&180u32), game.nullifier);
+}
+
+#[test]
+fn test_tagged_and_untagged_nullifiers_differ() {
+    let ts = setup();
+    let untagged = ts
+        .client
+        .derive_nullifier(&181u32, &ts.player1, &ts.player2, &None);
+    ts.client.set_domain_separation(&true);
+    let tagged = ts
+        .client
+        .derive_nullifier(&181u32, &ts.player1, &ts.player2, &None);
+    assert_ne!(tagged, untagged);
+    assert_eq!(
+        tagged,
+        expected_nullifier(
+            &ts.env,
+            true,
+            &ts.client.address,
+            181,
+            &ts.player1,
+            &ts.player2
+        )
+    );
+
+    start(&ts, 181);
+    let game = ts.client.get_game(&181u32);
+    assert_eq!(game.derivation_version, DERIVATION_V1);
+    assert_eq!(game.nullifier, tagged);
+}
+
+#[test]
+fn test_nullifier_scoped_to_contract() {
+    let ts = setup();
+    let other = EatherGridContractClient::new(
+        &ts.env,
+        &ts.env.register(
+            EatherGridContract,
+            (&ts.client.get_admin(), None::<Address>, &ts.verifier_addr),
+        ),
+    );
+    start(&ts, 182);
+    other.start_game(
+        &182u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+
+    let ours = ts.client.get_nullifier(&182u32);
+    let theirs = other.get_nullifier(&182u32);
+    assert_ne!(ours, theirs);
+    assert_eq!(
+        theirs,
+        expected_nullifier(
+            &ts.env,
+            false,
+            &other.address,
+            182,
+            &ts.player1,
+            &ts.player2
+        )
+    );
+}
+
+#[test]
+fn test_nullifier_binds_deadline() {
+    let ts = setup();
+    start_with_deadline(&ts, 183);
+    let game = ts.client.get_game(&183u32);
+    assert_eq!(
+        game.nullifier,
+        ts.client
+            .derive_nullifier(&183u32, &ts.player1, &ts.player2, &Some(150u32))
+    );
+    assert_ne!(
+        game.nullifier,
+        ts.client
+            .derive_nullifier(&183u32, &ts.player1, &ts.player2, &None)
+    );
+
+    let params = ts.client.get_derivation_params(&183u32);
+    assert_eq!(params.deadline_ledger, Some(150));
+    assert_eq!(ts.client.compute_target_variant(&params), game.nullifier);
+}
+
+#[test]
+fn test_domain_tag_query() {
+    let ts = setup();
+    assert_eq!(
+        ts.client.get_domain_tag(),
+        Bytes::from_slice(&ts.env, b"EATHER-GRID-V1")
+    );
+}
+
+// ============================================================================
+// Verifier Usage
+// ============================================================================
+
+#[test]
+fn test_games_verified_by_tracks_each_verifier() {
+    let ts = setup();
+    let old_verifier = ts.verifier_addr.clone();
+    let hash = start(&ts, 190);
+    start(&ts, 191);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+
+    ts.client
+        .submit_zk_proof(&190u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+    ts.client
+        .submit_zk_proof(&190u32, &ts.player2, &valid_proof(&ts.env), &pi, &40u32);
+
+    let new_verifier = ts.env.register(MockVerifier, ());
+    ts.client.set_verifier(&new_verifier);
+    ts.client
+        .submit_zk_proof(&191u32, &ts.player1, &valid_proof(&ts.env), &pi, &50u32);
+
+    assert_eq!(
+        ts.client.games_verified_by(&old_verifier),
+        Vec::from_array(&ts.env, [190u32])
+    );
+    assert_eq!(
+        ts.client.games_verified_by(&new_verifier),
+        Vec::from_array(&ts.env, [191u32])
+    );
+}
+
+#[test]
+fn test_games_verified_by_unknown_verifier_is_empty() {
+    let ts = setup();
+    let unused = Address::generate(&ts.env);
+    assert!(ts.client.games_verified_by(&unused).is_empty());
+}
+
+// ============================================================================
+// Stake-Aware Settlement
+// ============================================================================
+
+/// Route the default (no category) hub to a fresh `RecordingHub`.
+fn use_recording_hub(ts: &TestSetup) -> RecordingHubClient<'static> {
+    let hub = ts.env.register(RecordingHub, ());
+    ts.client.set_hub(&hub);
+    RecordingHubClient::new(&ts.env, &hub)
+}
+
+#[test]
+fn test_stake_aware_settlement_reports_asymmetric_stakes() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_stake_aware_settlement(&true);
+
+    let hash = test_treasure_hash(&ts.env);
+    ts.client
+        .start_game(&200u32, &ts.player1, &ts.player2, &300i128, &100i128, &hash);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&200u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&200u32);
+
+    assert_eq!(
+        hub.settled(),
+        Vec::from_array(&ts.env, [(200u32, ts.player2.clone(), 100i128, 300i128)])
+    );
+    assert!(hub.ended().is_empty());
+}
+
+#[test]
+fn test_boolean_settlement_when_flag_off() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+
+    let hash = test_treasure_hash(&ts.env);
+    ts.client
+        .start_game(&201u32, &ts.player1, &ts.player2, &300i128, &100i128, &hash);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&201u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&201u32);
+
+    assert!(hub.settled().is_empty());
+    assert_eq!(hub.ended(), Vec::from_array(&ts.env, [(201u32, 0u32)]));
+}
+
+#[test]
+fn test_stake_aware_settlement_without_winner_uses_end_game() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_stake_aware_settlement(&true);
+    start(&ts, 202);
+    ts.client.force_resolve(&202u32, &None);
+
+    assert!(hub.settled().is_empty());
+    assert_eq!(hub.ended(), Vec::from_array(&ts.env, [(202u32, 3u32)]));
+}
+
+// ============================================================================
+// Submission Authorization
+// ============================================================================
+
+#[test]
+fn test_submission_auth_binds_proof_and_inputs() {
+    let ts = setup();
+    let hash = start(&ts, 210);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    ts.client
+        .submit_zk_proof(&210u32, &ts.player1, &proof, &pi, &50u32);
+
+    let proof_hash: BytesN<32> = ts.env.crypto().keccak256(&proof).into();
+    assert_eq!(
+        ts.env.auths(),
+        std::vec![(
+            ts.player1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    ts.client.address.clone(),
+                    Symbol::new(&ts.env, "submit_zk_proof"),
+                    vec![
+                        &ts.env,
+                        210u32.into_val(&ts.env),
+                        proof_hash.into_val(&ts.env),
+                        pi.into_val(&ts.env),
+                        50u32.into_val(&ts.env),
+                    ],
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_signature_for_one_proof_cannot_authorize_another() {
+    let ts = setup();
+    let hash = start(&ts, 211);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let signed_proof = valid_proof(&ts.env);
+    let other_proof = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+    let signed_hash: BytesN<32> = ts.env.crypto().keccak256(&signed_proof).into();
+
+    ts.env.mock_auths(&[MockAuth {
+        address: &ts.player1,
+        invoke: &MockAuthInvoke {
+            contract: &ts.client.address,
+            fn_name: "submit_zk_proof",
+            args: (211u32, signed_hash, pi.clone(), 50u32).into_val(&ts.env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = ts
+        .client
+        .try_submit_zk_proof(&211u32, &ts.player1, &other_proof, &pi, &50u32);
+    assert!(
+        result.is_err(),
+        "auth for another proof must not be accepted"
+    );
+}
+
+// ============================================================================
+// Muxed Addresses
+// ============================================================================
+
+/// Start a game between two freshly generated account (G...) players, which
+/// unlike contract addresses can be multiplexed.
+fn start_account_game(ts: &TestSetup, session_id: u32) -> (Address, Address) {
+    let p1 = MuxedAddress::generate(&ts.env).address();
+    let p2 = MuxedAddress::generate(&ts.env).address();
+    ts.client.start_game(
+        &session_id,
+        &p1,
+        &p2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+    (p1, p2)
+}
+
+#[test]
+fn test_muxed_submission_matches_base_player() {
+    let ts = setup();
+    let (p1, _) = start_account_game(&ts, 220);
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+    let muxed_p1 = MuxedAddress::new(&p1, 42);
+
+    ts.client
+        .submit_zk_proof(&220u32, &muxed_p1, &valid_proof(&ts.env), &pi, &50u32);
+    assert_eq!(ts.client.get_game(&220u32).player1_energy, Some(50));
+}
+
+#[test]
+fn test_muxed_start_is_normalized() {
+    let ts = setup();
+    let p1 = MuxedAddress::generate(&ts.env).address();
+    let p2 = MuxedAddress::generate(&ts.env).address();
+    ts.client.start_game(
+        &221u32,
+        MuxedAddress::new(&p1, 7),
+        &p2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+    let game = ts.client.get_game(&221u32);
+    assert_eq!(game.player1, p1);
+    assert_eq!(
+        game.nullifier,
+        ts.client.derive_nullifier(&221u32, &p1, &p2, &None)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot play against yourself")]
+fn test_muxed_self_play_rejected() {
+    let ts = setup();
+    let p1 = MuxedAddress::generate(&ts.env).address();
+    ts.client.start_game(
+        &222u32,
+        MuxedAddress::new(&p1, 1),
+        MuxedAddress::new(&p1, 2),
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+}
+
+#[test]
+fn test_muxed_non_player_rejected() {
+    let ts = setup();
+    start_account_game(&ts, 223);
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+    let stranger = MuxedAddress::generate(&ts.env);
+    let result =
+        ts.client
+            .try_submit_zk_proof(&223u32, &stranger, &valid_proof(&ts.env), &pi, &50u32);
+    assert_error(&result, Error::MuxedAddressMismatch);
+}
+
+// ============================================================================
+// Auto-Resolve
+// ============================================================================
+
+#[test]
+fn test_second_verification_auto_resolves() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_auto_resolve(&true);
+    let hash = start(&ts, 230);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+
+    let first = ts
+        .client
+        .submit_zk_proof(&230u32, &ts.player1, &valid_proof(&ts.env), &pi, &70u32);
+    assert_eq!(first, None);
+    assert!(!ts.client.get_game(&230u32).resolved);
+
+    let second =
+        ts.client
+            .submit_zk_proof(&230u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(second, Some(Outcome::Player2Won));
+    assert!(ts.client.get_game(&230u32).resolved);
+
+    // A later resolve_game is idempotent and does not notify the hub again.
+    assert_eq!(ts.client.resolve_game(&230u32), Outcome::Player2Won);
+    assert_eq!(hub.ended(), Vec::from_array(&ts.env, [(230u32, 1u32)]));
+}
+
+#[test]
+fn test_auto_resolve_off_by_default() {
+    let ts = setup();
+    let hash = start(&ts, 231);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&231u32, &ts.player1, &valid_proof(&ts.env), &pi, &70u32);
+    let second =
+        ts.client
+            .submit_zk_proof(&231u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(second, None);
+    assert!(!ts.client.get_game(&231u32).resolved);
+}
+
+#[test]
+fn test_per_game_auto_resolve() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    let hash = start_with(
+        &ts,
+        947,
+        &GameOptions {
+            auto_resolve: true,
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    assert!(ts.client.get_game(&947u32).auto_resolve);
+
+    let first = ts
+        .client
+        .submit_zk_proof(&947u32, &ts.player1, &valid_proof(&ts.env), &pi, &70u32);
+    assert_eq!(first, None);
+    assert!(!ts.client.get_game(&947u32).resolved);
+
+    let second =
+        ts.client
+            .submit_zk_proof(&947u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(second, Some(Outcome::Player2Won));
+    assert_eq!(ts.client.resolve_game(&947u32), Outcome::Player2Won);
+    assert_eq!(hub.ended(), Vec::from_array(&ts.env, [(947u32, 1u32)]));
+
+    // Other games still wait for resolve_game.
+    start_resolvable(&ts, 948);
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+    let second =
+        ts.client
+            .submit_zk_proof(&948u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(second, None);
+}
+
+// ============================================================================
+// Session Names
+// ============================================================================
+
+#[test]
+fn test_named_session_resolves_to_id() {
+    let ts = setup();
+    let name = Bytes::from_slice(&ts.env, b"final-match");
+    ts.client.start_game_named(
+        &name,
+        &240u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+
+    assert_eq!(ts.client.resolve_session_name(&name), 240u32);
+    assert_eq!(ts.client.get_game(&240u32).player1, ts.player1);
+}
+
+#[test]
+fn test_duplicate_session_name_rejected() {
+    let ts = setup();
+    let name = Bytes::from_slice(&ts.env, b"final-match");
+    let hash = test_treasure_hash(&ts.env);
+    ts.client.start_game_named(
+        &name,
+        &241u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+    );
+
+    let result = ts.client.try_start_game_named(
+        &name,
+        &242u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+    );
+    assert_error(&result, Error::NameTaken);
+    assert_eq!(ts.client.resolve_session_name(&name), 241u32);
+    assert_error(&ts.client.try_get_game(&242u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_session_name_length_capped() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    for name in [
+        Bytes::new(&ts.env),
+        Bytes::from_slice(&ts.env, &[b'a'; MAX_SESSION_NAME_LEN as usize + 1]),
+    ] {
+        let result = ts.client.try_start_game_named(
+            &name,
+            &243u32,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &hash,
+        );
+        assert_error(&result, Error::InvalidName);
+    }
+}
+
+#[test]
+fn test_session_name_released_with_game() {
+    let ts = setup();
+    let name = Bytes::from_slice(&ts.env, b"final-match");
+    let hash = test_treasure_hash(&ts.env);
+    ts.client.start_game_named(
+        &name,
+        &244u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+    );
+    assert_eq!(ts.client.storage_report().session_names, 1);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&244u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&244u32);
+    ts.client.close_session(&244u32);
+
+    assert_error(
+        &ts.client.try_resolve_session_name(&name),
+        Error::GameNotFound,
+    );
+    assert_eq!(ts.client.storage_report().session_names, 0);
+    ts.client.start_game_named(
+        &name,
+        &245u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &hash,
+    );
+    assert_eq!(ts.client.resolve_session_name(&name), 245u32);
+}
+
+#[test]
+fn test_unknown_session_name() {
+    let ts = setup();
+    let name = Bytes::from_slice(&ts.env, b"no-such-match");
+    assert_error(
+        &ts.client.try_resolve_session_name(&name),
+        Error::GameNotFound,
+    );
+}
+
+// ============================================================================
+// Player Session Index
+// ============================================================================
+
+#[test]
+fn test_player_sessions_indexed_on_start() {
+    let ts = setup();
+    start(&ts, 250);
+    start(&ts, 251);
+
+    let expected = Vec::from_array(&ts.env, [250u32, 251]);
+    assert_eq!(ts.client.get_player_sessions(&ts.player1), expected);
+    assert_eq!(ts.client.get_player_sessions(&ts.player2), expected);
+    assert_eq!(ts.client.player_session_count(&ts.player1), 2);
+}
+
+#[test]
+fn test_player_session_cap_evicts_oldest_resolved() {
+    let ts = setup();
+    ts.client.set_max_player_sessions(&3);
+    let hash = start(&ts, 252);
+    start(&ts, 253);
+    start(&ts, 254);
+
+    // Resolve the middle session: it is the oldest resolved entry.
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&253u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&253u32);
+
+    start(&ts, 255);
+    assert_eq!(
+        ts.client.get_player_sessions(&ts.player1),
+        Vec::from_array(&ts.env, [252u32, 254, 255])
+    );
+    assert_eq!(ts.client.player_session_count(&ts.player1), 3);
+}
+
+#[test]
+fn test_player_session_cap_evicts_oldest_when_all_live() {
+    let ts = setup();
+    ts.client.set_max_player_sessions(&2);
+    start(&ts, 256);
+    start(&ts, 257);
+    start(&ts, 258);
+
+    assert_eq!(
+        ts.client.get_player_sessions(&ts.player2),
+        Vec::from_array(&ts.env, [257u32, 258])
+    );
+}
+
+#[test]
+fn test_player_dashboard_reports_each_phase() {
+    let ts = setup();
+    start(&ts, 720);
+    let hash = start(&ts, 721);
+    start(&ts, 722);
+    start(&ts, 723);
+    start(&ts, 724);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    ts.client
+        .submit_zk_proof(&721u32, &ts.player1, &proof, &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&722u32, &ts.player1, &proof, &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&722u32, &ts.player2, &proof, &pi, &20u32);
+    for session_id in [723u32, 724] {
+        ts.client
+            .submit_zk_proof(&session_id, &ts.player2, &proof, &pi, &10u32);
+        ts.client.resolve_game(&session_id);
+    }
+    // Reclaimed games drop out of the dashboard.
+    ts.client.reclaim_game(&724u32);
+
+    assert_eq!(
+        ts.client.player_dashboard(&ts.player1),
+        vec![
+            &ts.env,
+            (720u32, GamePhase::Open),
+            (721u32, GamePhase::OneVerified),
+            (722u32, GamePhase::BothVerified),
+            (723u32, GamePhase::Resolved),
+        ]
+    );
+    let stranger = Address::generate(&ts.env);
+    assert_eq!(ts.client.player_dashboard(&stranger), Vec::new(&ts.env));
+}
+
+// ============================================================================
+// Debug Mode
+// ============================================================================
+
+#[test]
+fn test_debug_mode_records_mismatched_inputs() {
+    let ts = setup();
+    ts.client.set_debug_mode(&true);
+    start(&ts, 260);
+    let wrong = Bytes::from_slice(&ts.env, &[0xCD; 32]);
+
+    // The submission still fails; the client records the bytes separately.
+    let result =
+        ts.client
+            .try_submit_zk_proof(&260u32, &ts.player2, &valid_proof(&ts.env), &wrong, &10u32);
+    assert_error(&result, Error::PublicInputMismatch);
+    ts.client
+        .record_rejected_inputs(&260u32, &ts.player2, &wrong);
+
+    assert_eq!(
+        ts.client.get_last_rejected_inputs(&260u32, &ts.player2),
+        Some(wrong)
+    );
+    assert_eq!(
+        ts.client.get_last_rejected_inputs(&260u32, &ts.player1),
+        None
+    );
+
+    // The rejected attempt did not count as a submission.
+    let game = ts.client.get_game(&260u32);
+    assert_eq!(game.submissions, 0);
+    assert_eq!(game.player2_energy, None);
+}
+
+#[test]
+fn test_record_rejected_inputs_is_bounded() {
+    let ts = setup();
+    ts.client.set_debug_mode(&true);
+    start_resolvable(&ts, 973);
+    let oversized = Bytes::from_slice(&ts.env, &[0xCD; MAX_REJECTED_INPUTS_LEN as usize + 1]);
+    assert_error(
+        &ts.client
+            .try_record_rejected_inputs(&973u32, &ts.player2, &oversized),
+        Error::InputsTooLarge,
+    );
+
+    ts.client.resolve_game(&973u32);
+    let wrong = Bytes::from_slice(&ts.env, &[0xCD; 32]);
+    assert_error(
+        &ts.client
+            .try_record_rejected_inputs(&973u32, &ts.player2, &wrong),
+        Error::GameAlreadyResolved,
+    );
+    ts.client.set_frozen_resolved(&true);
+    assert_error(
+        &ts.client
+            .try_record_rejected_inputs(&973u32, &ts.player2, &wrong),
+        Error::GameFrozen,
+    );
+}
+
+#[test]
+fn test_mismatch_not_recorded_without_debug_mode() {
+    let ts = setup();
+    start(&ts, 261);
+    let wrong = Bytes::from_slice(&ts.env, &[0xCD; 32]);
+
+    let result =
+        ts.client
+            .try_submit_zk_proof(&261u32, &ts.player1, &valid_proof(&ts.env), &wrong, &10u32);
+    assert_error(&result, Error::PublicInputMismatch);
+    ts.client
+        .record_rejected_inputs(&261u32, &ts.player1, &wrong);
+    assert_eq!(
+        ts.client.get_last_rejected_inputs(&261u32, &ts.player1),
+        None
+    );
+}
+
+#[test]
+fn test_last_rejected_inputs_requires_player() {
+    let ts = setup();
+    start(&ts, 262);
+    let stranger = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_get_last_rejected_inputs(&262u32, &stranger),
+        Error::NotPlayer,
+    );
+}
+
+// ============================================================================
+// Resolution Reward
+// ============================================================================
+
+/// Start a game and submit player 1's proof so it can be resolved.
+fn start_resolvable(ts: &TestSetup, session_id: u32) {
+    let hash = start(ts, session_id);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&session_id, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+}
+
+#[test]
+fn test_player_resolver_earns_reward() {
+    let ts = setup();
+    ts.client.set_resolution_reward(&25);
+    ts.client.set_players_only_reward(&true);
+    start_resolvable(&ts, 270);
+
+    let outcome = ts.client.resolve_game_as(&270u32, &ts.player2);
+    assert_eq!(outcome, Outcome::Player1Won);
+    assert_eq!(ts.client.get_reward_balance(&ts.player2), 25);
+}
+
+#[test]
+fn test_keeper_settles_without_reward_when_players_only() {
+    let ts = setup();
+    ts.client.set_resolution_reward(&25);
+    ts.client.set_players_only_reward(&true);
+    start_resolvable(&ts, 271);
+    let keeper = Address::generate(&ts.env);
+
+    ts.client.resolve_game_as(&271u32, &keeper);
+    assert!(ts.client.get_game(&271u32).resolved);
+    assert_eq!(ts.client.get_reward_balance(&keeper), 0);
+}
+
+#[test]
+fn test_keeper_earns_reward_by_default() {
+    let ts = setup();
+    ts.client.set_resolution_reward(&25);
+    start_resolvable(&ts, 272);
+    let keeper = Address::generate(&ts.env);
+
+    ts.client.resolve_game_as(&272u32, &keeper);
+    assert_eq!(ts.client.get_reward_balance(&keeper), 25);
+
+    // Re-resolving an already settled game earns nothing more.
+    ts.client.resolve_game_as(&272u32, &keeper);
+    assert_eq!(ts.client.get_reward_balance(&keeper), 25);
+}
+
+// ============================================================================
+// Bridge Event
+// ============================================================================
+
+#[test]
+fn test_resolution_publishes_bridge_payload() {
+    let ts = setup();
+    let hash = start(&ts, 0x0102_0304);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client.submit_zk_proof(
+        &0x0102_0304u32,
+        &ts.player2,
+        &valid_proof(&ts.env),
+        &pi,
+        &10u32,
+    );
+    ts.client.resolve_game(&0x0102_0304u32);
+
+    let mut expected = Bytes::from_array(&ts.env, &[0x04, 0x03, 0x02, 0x01, 0x01]);
+    for player in [&ts.player1, &ts.player2] {
+        let digest: BytesN<32> = ts
+            .env
+            .crypto()
+            .keccak256(&player.to_string().to_bytes())
+            .into();
+        expected.append(&digest.into());
+    }
+    expected.push_back(0x00);
+    assert_eq!(expected.len(), 70);
+
+    let events = ts.env.events().all().filter_by_contract(&ts.client.address);
+    assert_eq!(
+        events,
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (
+                    symbol_short!("game"),
+                    symbol_short!("resolved"),
+                    0x0102_0304u32
+                )
+                    .into_val(&ts.env),
+                Outcome::Player2Won.into_val(&ts.env),
+            ),
+            (
+                ts.client.address.clone(),
+                (symbol_short!("bridge"), symbol_short!("result")).into_val(&ts.env),
+                expected.into_val(&ts.env),
+            ),
+        ]
+    );
+}
+
+// ============================================================================
+// Operational Check
+// ============================================================================
+
+#[test]
+fn test_configured_contract_is_operational() {
+    let ts = setup();
+    assert!(ts.client.is_operational());
+}
+
+#[test]
+fn test_paused_contract_is_not_operational() {
+    let ts = setup();
+    assert!(ts.client.is_operational());
+    ts.client.pause();
+    assert!(!ts.client.is_operational());
+    ts.client.unpause();
+    assert!(ts.client.is_operational());
+}
+
+#[test]
+fn test_missing_verifier_is_not_operational() {
+    let ts = setup();
+    ts.env.as_contract(&ts.client.address, || {
+        ts.env
+            .storage()
+            .instance()
+            .remove(&crate::DataKey::VerifierAddress);
+    });
+    assert!(!ts.client.is_operational());
+}
+
+#[test]
+fn test_misconfigured_verifier_is_not_operational() {
+    let ts = setup();
+    ts.client.set_verifier(&ts.client.get_hub());
+    assert!(!ts.client.is_operational());
+
+    ts.client.set_verifier(&ts.client.address);
+    assert!(!ts.client.is_operational());
+}
+
+#[test]
+fn test_admin_doubling_as_verifier_or_hub_is_not_operational() {
+    let ts = setup();
+    let admin = ts.client.get_admin();
+    let set_instance = |key: crate::DataKey, value: &Address| {
+        ts.env.as_contract(&ts.client.address, || {
+            ts.env.storage().instance().set(&key, value);
+        });
+    };
+
+    set_instance(crate::DataKey::Admin, &ts.verifier_addr);
+    assert!(!ts.client.is_operational());
+    set_instance(crate::DataKey::Admin, &ts.client.get_hub());
+    assert!(!ts.client.is_operational());
+    set_instance(crate::DataKey::Admin, &admin);
+    assert!(ts.client.is_operational());
+
+    // An account, not a contract, as verifier.
+    set_instance(crate::DataKey::VerifierAddress, &Address::generate(&ts.env));
+    assert!(!ts.client.is_operational());
+}
+
+// ============================================================================
+// Tie Default
+// ============================================================================
+
+/// Start a game under `tie_default`, submit equal energies and resolve it.
+/// Returns the winner and the outcome code the hub received.
+fn resolve_tie(ts: &TestSetup, session_id: u32, tie_default: TieDefault) -> (Option<Address>, u32) {
+    let hub = use_recording_hub(ts);
+    ts.client.set_tie_default(&tie_default);
+    let hash = start(ts, session_id);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    for player in [&ts.player1, &ts.player2] {
+        ts.client
+            .submit_zk_proof(&session_id, player, &valid_proof(&ts.env), &pi, &40u32);
+    }
+
+    assert_eq!(
+        ts.client.resolve_game(&session_id),
+        Outcome::BothFoundTreasure
+    );
+    let (ended_id, outcome) = hub.ended().get(0).unwrap();
+    assert_eq!(ended_id, session_id);
+    (ts.client.get_winner(&session_id), outcome)
+}
+
+#[test]
+fn test_tie_is_a_draw_under_every_tie_default() {
+    let ts = setup();
+    assert_eq!(ts.client.get_tie_default(), TieDefault::Player1);
+    for (session_id, tie_default) in [
+        (280u32, TieDefault::Player1),
+        (281, TieDefault::Player2),
+        (282, TieDefault::Draw),
+        (283, TieDefault::RandomFromSeed),
+    ] {
+        let (winner, outcome) = resolve_tie(&ts, session_id, tie_default);
+        assert_eq!(winner, None);
+        assert_eq!(outcome, 2);
+    }
+}
+
+#[test]
+fn test_tie_under_stake_aware_settlement_ends_as_draw() {
+    let ts = setup();
+    ts.client.set_stake_aware_settlement(&true);
+    let (winner, outcome) = resolve_tie(&ts, 969, TieDefault::Player2);
+    assert_eq!(winner, None);
+    assert_eq!(outcome, 2);
+}
+
+#[test]
+fn test_tie_under_claim_required_needs_no_claim() {
+    let ts = setup();
+    ts.client.set_claim_required(&true);
+    let (winner, outcome) = resolve_tie(&ts, 970, TieDefault::Player1);
+    assert_eq!(winner, None);
+    assert_eq!(outcome, 2);
+    assert!(!ts.client.get_game(&970u32).awaiting_claim);
+    assert_error(
+        &ts.client.try_claim_winnings(&970u32),
+        Error::NothingToClaim,
+    );
+}
+
+#[test]
+fn test_tie_default_snapshotted_at_start() {
+    let ts = setup();
+    start(&ts, 284);
+    ts.client.set_tie_default(&TieDefault::Draw);
+    assert_eq!(ts.client.get_game(&284u32).tie_default, TieDefault::Player1);
+}
+
+// ============================================================================
+// Fee Config
+// ============================================================================
+
+#[test]
+fn test_fee_config_defaults_and_updates() {
+    let ts = setup();
+    assert_eq!(
+        ts.client.get_fee_config(),
+        FeeConfig {
+            resolution_reward: 0,
+            players_only_reward: false,
+            rounding_policy: RoundingPolicy::ToProtocol,
+        }
+    );
+
+    ts.client.set_resolution_reward(&40);
+    ts.client.set_players_only_reward(&true);
+    assert_eq!(
+        ts.client.get_fee_config(),
+        FeeConfig {
+            resolution_reward: 40,
+            players_only_reward: true,
+            rounding_policy: RoundingPolicy::ToProtocol,
+        }
+    );
+}
+
+// ============================================================================
+// Chunked Proofs
+// ============================================================================
+
+#[test]
+fn test_chunked_proof_assembles_and_verifies() {
+    let ts = setup();
+    let hash = start(&ts, 290);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+
+    for (index, range) in [(0u32, 0..20), (1, 20..40), (2, 40..64)] {
+        let chunk = proof.slice(range.start..range.end);
+        ts.client
+            .submit_proof_chunk(&290u32, &ts.player1, &index, &chunk, &3u32);
+    }
+    ts.client.finalize_proof(&290u32, &ts.player1, &pi, &15u32);
+
+    assert_eq!(ts.client.get_game(&290u32).player1_energy, Some(15));
+}
+
+#[test]
+fn test_out_of_order_chunk_rejected() {
+    let ts = setup();
+    start(&ts, 291);
+    let chunk = Bytes::from_slice(&ts.env, &[0x01; 16]);
+
+    ts.client
+        .submit_proof_chunk(&291u32, &ts.player1, &0u32, &chunk, &3u32);
+    let result = ts
+        .client
+        .try_submit_proof_chunk(&291u32, &ts.player1, &2u32, &chunk, &3u32);
+    assert_error(&result, Error::IncompleteProof);
+}
+
+#[test]
+fn test_finalize_with_missing_chunks_rejected() {
+    let ts = setup();
+    let hash = start(&ts, 292);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let chunk = Bytes::from_slice(&ts.env, &[0x01; 16]);
+
+    let result = ts
+        .client
+        .try_finalize_proof(&292u32, &ts.player1, &pi, &15u32);
+    assert_error(&result, Error::IncompleteProof);
+
+    ts.client
+        .submit_proof_chunk(&292u32, &ts.player1, &0u32, &chunk, &2u32);
+    let result = ts
+        .client
+        .try_finalize_proof(&292u32, &ts.player1, &pi, &15u32);
+    assert_error(&result, Error::IncompleteProof);
+    assert_eq!(ts.client.get_game(&292u32).player1_energy, None);
+}
+
+// ============================================================================
+// Verifier Mode
+// ============================================================================
+
+#[test]
+fn test_per_game_verifier_mode_override() {
+    let ts = setup();
+    let bool_verifier = ts.env.register(MockBoolVerifier, ());
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+
+    // Game 300 runs against the bool verifier via a per-game override.
+    ts.client.set_verifier(&bool_verifier);
+    start_with(
+        &ts,
+        300,
+        &GameOptions {
+            verifier_mode: VerifierMode::Bool,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        ts.client.get_game(&300u32).verifier_mode,
+        VerifierMode::Bool
+    );
+    let result =
+        ts.client
+            .try_submit_zk_proof(&300u32, &ts.player1, &invalid_proof(&ts.env), &pi, &10u32);
+    assert_error(&result, Error::InvalidProof);
+    ts.client
+        .submit_zk_proof(&300u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+
+    // Game 301 uses the global default against the trapping verifier.
+    ts.client.set_verifier(&ts.verifier_addr);
+    start(&ts, 301);
+    assert_eq!(
+        ts.client.get_game(&301u32).verifier_mode,
+        VerifierMode::Trap
+    );
+    ts.client
+        .submit_zk_proof(&301u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    let result =
+        ts.client
+            .try_submit_zk_proof(&301u32, &ts.player2, &invalid_proof(&ts.env), &pi, &10u32);
+    assert!(result.is_err());
+    assert_eq!(ts.client.get_game(&301u32).player2_energy, None);
+}
+
+#[test]
+fn test_global_verifier_mode_applies_without_override() {
+    let ts = setup();
+    assert_eq!(ts.client.get_verifier_mode(), VerifierMode::Trap);
+    ts.client.set_verifier_mode(&VerifierMode::Bool);
+    start(&ts, 302);
+    assert_eq!(
+        ts.client.get_game(&302u32).verifier_mode,
+        VerifierMode::Bool
+    );
+}
+
+// ============================================================================
+// Phase Counts
+// ============================================================================
+
+fn counts(open: u32, one_verified: u32, both_verified: u32, resolved: u32) -> PhaseCounts {
+    PhaseCounts {
+        open,
+        one_verified,
+        both_verified,
+        resolved,
+    }
+}
+
+#[test]
+fn test_phase_counts_track_transitions() {
+    let ts = setup();
+    assert_eq!(ts.client.phase_counts(), counts(0, 0, 0, 0));
+
+    let hash = start(&ts, 310);
+    start(&ts, 311);
+    assert_eq!(ts.client.phase_counts(), counts(2, 0, 0, 0));
+
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&310u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(ts.client.phase_counts(), counts(1, 1, 0, 0));
+
+    ts.client
+        .submit_zk_proof(&310u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(ts.client.phase_counts(), counts(1, 0, 1, 0));
+
+    ts.client.resolve_game(&310u32);
+    assert_eq!(ts.client.phase_counts(), counts(1, 0, 0, 1));
+
+    // Re-resolving does not count twice.
+    ts.client.resolve_game(&310u32);
+    assert_eq!(ts.client.phase_counts(), counts(1, 0, 0, 1));
+
+    ts.client.force_resolve(&311u32, &None);
+    assert_eq!(ts.client.phase_counts(), counts(0, 0, 0, 2));
+}
+
+// ============================================================================
+// Verifier Address Validation
+// ============================================================================
+
+#[test]
+fn test_set_verifier_rejects_account_address() {
+    let ts = setup();
+    let account = MuxedAddress::generate(&ts.env).address();
+    assert_error(&ts.client.try_set_verifier(&account), Error::NotAContract);
+    assert_eq!(ts.client.get_verifier(), ts.verifier_addr);
+}
+
+#[test]
+fn test_set_verifier_rejects_undeployed_contract_address() {
+    let ts = setup();
+    let missing = Address::generate(&ts.env);
+    assert_error(&ts.client.try_set_verifier(&missing), Error::NotAContract);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_constructor_rejects_account_verifier() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let account = MuxedAddress::generate(&env).address();
+    env.register(EatherGridContract, (&admin, Some(hub_addr), &account));
+}
+
+#[test]
+#[should_panic(expected = "Admin and verifier must differ")]
+fn test_constructor_rejects_admin_as_verifier() {
+    let env = Env::default();
+    let verifier = env.register(MockVerifier, ());
+    env.register(EatherGridContract, (&verifier, None::<Address>, &verifier));
+}
+
+#[test]
+#[should_panic(expected = "Game hub and verifier must differ")]
+fn test_constructor_rejects_hub_as_verifier() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let verifier = env.register(MockVerifier, ());
+    env.register(EatherGridContract, (&admin, Some(&verifier), &verifier));
+}
+
+#[test]
+#[should_panic(expected = "Admin and game hub must differ")]
+fn test_constructor_rejects_admin_as_hub() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let verifier = env.register(MockVerifier, ());
+    env.register(EatherGridContract, (&admin, Some(&admin), &verifier));
+}
+
+#[test]
+fn test_constructor_accepts_distinct_addresses() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub = env.register(MockGameHub, ());
+    let verifier = env.register(MockVerifier, ());
+    let cid = env.register(EatherGridContract, (&admin, Some(&hub), &verifier));
+    let client = EatherGridContractClient::new(&env, &cid);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_verifier(), verifier);
+    assert_eq!(client.get_schema_version(), VERSION);
+}
+
+// ============================================================================
+// Player Results
+// ============================================================================
+
+#[test]
+fn test_player_results_history() {
+    let ts = setup();
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+    for (session_id, winner) in [
+        (320u32, &ts.player1),
+        (321, &ts.player2),
+        (322, &ts.player1),
+    ] {
+        start(&ts, session_id);
+        ts.client
+            .submit_zk_proof(&session_id, winner, &valid_proof(&ts.env), &pi, &10u32);
+        ts.client.resolve_game(&session_id);
+    }
+
+    assert_eq!(
+        ts.client.player_results(&ts.player1, &10),
+        vec![&ts.env, (320u32, true), (321, false), (322, true)]
+    );
+    assert_eq!(
+        ts.client.player_results(&ts.player2, &2),
+        vec![&ts.env, (321u32, true), (322, false)]
+    );
+    assert_eq!(ts.client.player_results(&ts.player1, &0).len(), 0);
+}
+
+#[test]
+fn test_player_results_bounded() {
+    let ts = setup();
+    let pi = treasure_hash_as_bytes(&ts.env, &test_treasure_hash(&ts.env));
+    for session_id in 1000u32..1052 {
+        start(&ts, session_id);
+        ts.client
+            .submit_zk_proof(&session_id, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+        ts.client.resolve_game(&session_id);
+    }
+
+    let results = ts.client.player_results(&ts.player1, &u32::MAX);
+    assert_eq!(results.len(), 50);
+    assert_eq!(results.get(0), Some((1002u32, true)));
+    assert_eq!(results.last(), Some((1051u32, true)));
+}
+
+// ============================================================================
+// Hub Migration
+// ============================================================================
+
+#[test]
+fn test_migrate_active_games_to_new_hub() {
+    let ts = setup();
+    let old_hub = ts.client.get_hub();
+    start(&ts, 330);
+    start(&ts, 331);
+    let hash = start(&ts, 332);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&332u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&332u32);
+
+    let new_hub = ts.env.register(RecordingHub, ());
+    assert_eq!(ts.client.active_session_count(), 2);
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &0), 2);
+    assert_eq!(ts.client.get_game(&330u32).hub, Some(new_hub.clone()));
+    assert_eq!(ts.client.get_game(&331u32).hub, Some(new_hub.clone()));
+    // Resolved games are no longer active and keep their hub.
+    assert_eq!(ts.client.get_game(&332u32).hub, Some(old_hub.clone()));
+    // Re-running the page is a no-op.
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &0), 0);
+
+    // Migrated games settle on the new hub.
+    ts.client
+        .submit_zk_proof(&330u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&330u32);
+    let recording = RecordingHubClient::new(&ts.env, &new_hub);
+    assert_eq!(recording.ended(), vec![&ts.env, (330u32, 1u32)]);
+}
+
+#[test]
+fn test_migration_drops_the_old_hubs_lock_id() {
+    let ts = setup();
+    let old_hub = ts.env.register(LockingHub, ());
+    ts.client.set_hub(&old_hub);
+    start_resolvable(&ts, 971);
+    assert_eq!(ts.client.get_game(&971u32).hub_lock_id, 7_971);
+
+    let new_hub = ts.env.register(RecordingHub, ());
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &0), 1);
+    assert_eq!(ts.client.get_game(&971u32).hub_lock_id, 0);
+
+    ts.client.resolve_game(&971u32);
+    let recording = RecordingHubClient::new(&ts.env, &new_hub);
+    assert_eq!(recording.ended(), vec![&ts.env, (971u32, 0u32)]);
+    assert!(LockingHubClient::new(&ts.env, &old_hub).ended().is_empty());
+}
+
+#[test]
+fn test_migration_is_paginated() {
+    let ts = setup();
+    for session_id in 340u32..395 {
+        start(&ts, session_id);
+    }
+    let new_hub = ts.env.register(RecordingHub, ());
+    assert_eq!(ts.client.active_session_count(), 55);
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &0), 25);
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &25), 25);
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &50), 5);
+    assert_eq!(ts.client.migrate_all_games_to_hub(&new_hub, &75), 0);
+    assert_eq!(ts.client.get_game(&394u32).hub, Some(new_hub.clone()));
+}
+
+// ============================================================================
+// Target Lookup
+// ============================================================================
+
+#[test]
+fn test_find_session_by_target() {
+    let ts = setup();
+    start(&ts, 400);
+    let target = BytesN::from_array(&ts.env, &[0x5A; 32]);
+    ts.client
+        .start_game(&401u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &target);
+    start(&ts, 402);
+
+    assert_eq!(
+        ts.client
+            .find_session_by_target(&treasure_hash_as_bytes(&ts.env, &target)),
+        Some(401)
+    );
+}
+
+#[test]
+fn test_find_session_by_unknown_target() {
+    let ts = setup();
+    start(&ts, 403);
+    let unknown = Bytes::from_slice(&ts.env, &[0x77; 32]);
+    assert_eq!(ts.client.find_session_by_target(&unknown), None);
+}
+
+// ============================================================================
+// Session Reuse Cooldown
+// ============================================================================
+
+#[test]
+fn test_session_reuse_within_cooldown_rejected() {
+    let ts = setup();
+    ts.client.set_session_reuse_cooldown(&10);
+    start_resolvable(&ts, 410);
+    ts.client.resolve_game(&410u32);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 9);
+    let hash = test_treasure_hash(&ts.env);
+    let result =
+        ts.client
+            .try_start_game(&410u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &hash);
+    assert_error(&result, Error::SessionReuseTooSoon);
+    assert!(ts.client.get_game(&410u32).resolved);
+}
+
+#[test]
+fn test_session_reuse_after_cooldown_allowed() {
+    let ts = setup();
+    ts.client.set_session_reuse_cooldown(&10);
+    start_resolvable(&ts, 411);
+    ts.client.resolve_game(&411u32);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 10);
+    start(&ts, 411);
+    assert!(!ts.client.get_game(&411u32).resolved);
+}
+
+#[test]
+fn test_session_reuse_rejected_while_claim_pending() {
+    let ts = setup();
+    ts.client.set_claim_required(&true);
+    start_resolvable(&ts, 413);
+    ts.client.resolve_game(&413u32);
+    assert!(ts.client.get_game(&413u32).awaiting_claim);
+
+    let hash = test_treasure_hash(&ts.env);
+    let result =
+        ts.client
+            .try_start_game(&413u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &hash);
+    assert_error(&result, Error::ClaimPending);
+
+    ts.client.claim_winnings(&413u32);
+    start(&ts, 413);
+}
+
+#[test]
+fn test_session_reuse_rejected_while_arbitration_pending() {
+    let ts = setup();
+    ts.client.set_escalation_window(&10);
+    let hash = test_treasure_hash(&ts.env);
+    start_arbitrated(&ts, 414);
+    ts.client.resolve_game(&414u32);
+    let restart = || {
+        ts.client
+            .try_start_game(&414u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &hash)
+    };
+    assert_error(&restart(), Error::EscalationOpen);
+
+    ts.client.escalate(&414u32, &ts.player2);
+    ts.env.ledger().with_mut(|l| l.sequence_number += 10);
+    assert_error(&restart(), Error::UnderArbitration);
+
+    ts.client.arbitrate(&414u32, &Outcome::Player2Won);
+    start(&ts, 414);
+}
+
+#[test]
+fn test_session_reuse_immediate_by_default() {
+    let ts = setup();
+    assert_eq!(ts.client.get_session_reuse_cooldown(), 0);
+    start_resolvable(&ts, 412);
+    ts.client.resolve_game(&412u32);
+    start(&ts, 412);
+    assert!(!ts.client.get_game(&412u32).resolved);
+}
+
+// ============================================================================
+// Verifier Call Args
+// ============================================================================
+
+#[test]
+fn test_verifier_call_args_match_submission() {
+    let ts = setup();
+    let hash = start(&ts, 420);
+    let proof = valid_proof(&ts.env);
+
+    let (public_inputs, proof_bytes) = ts.client.get_verifier_call_args(&420u32, &proof);
+    assert_eq!(public_inputs, treasure_hash_as_bytes(&ts.env, &hash));
+    assert_eq!(proof_bytes, proof);
+
+    // Exactly these arguments pass the submission checks.
+    ts.client
+        .would_accept_submission(&420u32, &ts.player1, &public_inputs);
+}
+
+// ============================================================================
+// Proportional TTL
+// ============================================================================
+
+/// TTL left on a game's storage entry.
+fn game_ttl(ts: &TestSetup, session_id: u32) -> u32 {
+    ts.env.as_contract(&ts.client.address, || {
+        ts.env
+            .storage()
+            .temporary()
+            .get_ttl(&crate::DataKey::Game(session_id))
+    })
+}
+
+#[test]
+fn test_submission_extends_ttl_proportionally() {
+    let ts = setup();
+    ts.client.set_proportional_ttl(&true);
+    ts.env.ledger().with_mut(|li| li.min_temp_entry_ttl = 16);
+    let hash = start(&ts, 430);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.env.ledger().with_mut(|li| li.sequence_number += 500_000);
+
+    // One of two submissions left: half the full TTL.
+    ts.client
+        .submit_zk_proof(&430u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(game_ttl(&ts, 430), 259_200);
+
+    // Budget spent: only the floor, and only once the TTL drops below it.
+    ts.env.ledger().with_mut(|li| li.sequence_number += 250_000);
+    ts.client
+        .submit_zk_proof(&430u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(game_ttl(&ts, 430), 17_280);
+}
+
+#[test]
+fn test_proportional_ttl_respects_max_entry_ttl() {
+    let ts = setup();
+    ts.client.set_proportional_ttl(&true);
+    ts.env.ledger().with_mut(|li| li.min_temp_entry_ttl = 16);
+    let hash = start(&ts, 431);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.env.ledger().with_mut(|li| {
+        li.sequence_number += 510_000;
+        li.max_entry_ttl = 100_000;
+    });
+
+    ts.client
+        .submit_zk_proof(&431u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(game_ttl(&ts, 431), 99_999);
+}
+
+// ============================================================================
+// Points Cap
+// ============================================================================
+
+#[test]
+fn test_per_game_points_cap_override() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    ts.client.set_max_points(&POINTS);
+    ts.client.set_game_max_points(&440u32, &(POINTS * 10));
+
+    // The high-roller game accepts stakes above the global cap.
+    ts.client.start_game(
+        &440u32,
+        &ts.player1,
+        &ts.player2,
+        &(POINTS * 5),
+        &POINTS,
+        &hash,
+    );
+    assert_eq!(ts.client.get_game(&440u32).player1_points, POINTS * 5);
+
+    // Every other game is still held to the global cap.
+    let result = ts.client.try_start_game(
+        &441u32,
+        &ts.player1,
+        &ts.player2,
+        &(POINTS * 5),
+        &POINTS,
+        &hash,
+    );
+    assert_error(&result, Error::PointsAboveCap);
+    start(&ts, 441);
+}
+
+#[test]
+fn test_points_cap_above_ceiling_rejected() {
+    let ts = setup();
+    assert_error(
+        &ts.client
+            .try_set_game_max_points(&442u32, &(MAX_POINTS_CEILING + 1)),
+        Error::InvalidPointsCap,
+    );
+    assert_error(&ts.client.try_set_max_points(&0), Error::InvalidPointsCap);
+    assert_eq!(ts.client.get_max_points(), None);
+}
+
+// ============================================================================
+// Hub Lock Ids
+// ============================================================================
+
+#[test]
+fn test_hub_lock_id_round_trips() {
+    let ts = setup();
+    let hub = ts.env.register(LockingHub, ());
+    ts.client.set_hub(&hub);
+    start_resolvable(&ts, 450);
+    assert_eq!(ts.client.get_game(&450u32).hub_lock_id, 7_450);
+
+    ts.client.resolve_game(&450u32);
+    let locking = LockingHubClient::new(&ts.env, &hub);
+    assert_eq!(locking.ended(), vec![&ts.env, (450u32, 0u32, 7_450u64)]);
+}
+
+#[test]
+fn test_hub_lock_id_settlement_reports_a_tie_as_a_draw() {
+    let ts = setup();
+    let hub = ts.env.register(LockingHub, ());
+    ts.client.set_hub(&hub);
+    let hash = start(&ts, 958);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    for player in [&ts.player1, &ts.player2] {
+        ts.client
+            .submit_zk_proof(&958u32, player, &valid_proof(&ts.env), &pi, &40u32);
+    }
+
+    ts.client.resolve_game(&958u32);
+    let locking = LockingHubClient::new(&ts.env, &hub);
+    assert_eq!(locking.ended(), vec![&ts.env, (958u32, 2u32, 7_958u64)]);
+}
+
+#[test]
+fn test_hub_lock_id_round_trips_on_stake_aware_settlement() {
+    let ts = setup();
+    let hub = ts.env.register(LockingHub, ());
+    ts.client.set_hub(&hub);
+    ts.client.set_stake_aware_settlement(&true);
+    start_resolvable(&ts, 968);
+
+    ts.client.resolve_game(&968u32);
+    let locking = LockingHubClient::new(&ts.env, &hub);
+    assert_eq!(
+        locking.settled(),
+        vec![
+            &ts.env,
+            (968u32, ts.player1.clone(), POINTS, POINTS, 7_968u64)
+        ]
+    );
+    assert!(locking.ended().is_empty());
+}
+
+#[test]
+fn test_hub_lock_id_round_trips_on_refund() {
+    let ts = setup();
+    let hub = ts.env.register(LockingHub, ());
+    ts.client.set_hub(&hub);
+    start_resolvable(&ts, 959);
+
+    ts.client.admin_refund(&959u32);
+    let locking = LockingHubClient::new(&ts.env, &hub);
+    assert_eq!(locking.refunded(), vec![&ts.env, (959u32, 7_959u64)]);
+    assert!(locking.ended().is_empty());
+}
+
+#[test]
+fn test_hub_without_lock_id_defaults_to_zero() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 451);
+    assert_eq!(ts.client.get_game(&451u32).hub_lock_id, 0);
+
+    ts.client.resolve_game(&451u32);
+    assert_eq!(hub.ended(), vec![&ts.env, (451u32, 0u32)]);
+}
+
+// ============================================================================
+// Derivation Params
+// ============================================================================
+
+#[test]
+fn test_derivation_params_reproduce_nullifier() {
+    let ts = setup();
+    ts.client.set_domain_separation(&true);
+    start(&ts, 460);
+
+    let params = ts.client.get_derivation_params(&460u32);
+    assert_eq!(params.version, DERIVATION_V1);
+    assert_eq!(params.hash, Symbol::new(&ts.env, "keccak256"));
+
+    // Rebuild the preimage from the individual inputs.
+    let mut preimage = params.domain_tag.clone();
+    preimage.extend_from_array(&params.session_id.to_be_bytes());
+    preimage.append(&params.player1.to_string().to_bytes());
+    preimage.append(&params.player2.to_string().to_bytes());
+    preimage.append(&params.contract.to_string().to_bytes());
+    assert_eq!(params.contract, ts.client.address);
+    assert_eq!(params.deadline_ledger, None);
+    assert_eq!(preimage, params.preimage);
+
+    let nullifier: BytesN<32> = ts.env.crypto().keccak256(&preimage).into();
+    assert_eq!(nullifier, ts.client.get_nullifier(&460u32));
+}
+
+#[test]
+fn test_derivation_params_untagged() {
+    let ts = setup();
+    start(&ts, 461);
+    let params = ts.client.get_derivation_params(&461u32);
+    assert_eq!(params.version, DERIVATION_V0);
+    assert!(params.domain_tag.is_empty());
+    let nullifier: BytesN<32> = ts.env.crypto().keccak256(&params.preimage).into();
+    assert_eq!(nullifier, ts.client.get_nullifier(&461u32));
+}
+
+#[test]
+fn test_compute_target_variant_reproduces_nullifier() {
+    let ts = setup();
+    ts.client.set_domain_separation(&true);
+    start(&ts, 462);
+    let params = ts.client.get_derivation_params(&462u32);
+    assert!(params.salt.is_empty());
+    assert_eq!(
+        ts.client.compute_target_variant(&params),
+        ts.client.get_nullifier(&462u32)
+    );
+}
+
+#[test]
+fn test_compute_target_variant_hash_and_salt() {
+    let ts = setup();
+    start(&ts, 463);
+    let mut params = ts.client.get_derivation_params(&463u32);
+
+    params.hash = Symbol::new(&ts.env, "sha256");
+    let sha: BytesN<32> = ts.env.crypto().sha256(&params.preimage).into();
+    assert_eq!(ts.client.compute_target_variant(&params), sha);
+
+    params.salt = Bytes::from_array(&ts.env, &[0xabu8; 4]);
+    let mut salted = params.preimage.clone();
+    salted.append(&params.salt);
+    let salted_sha: BytesN<32> = ts.env.crypto().sha256(&salted).into();
+    assert_eq!(ts.client.compute_target_variant(&params), salted_sha);
+
+    params.hash = Symbol::new(&ts.env, "keccak256");
+    let salted_keccak: BytesN<32> = ts.env.crypto().keccak256(&salted).into();
+    assert_eq!(ts.client.compute_target_variant(&params), salted_keccak);
+    assert_ne!(salted_keccak, ts.client.get_nullifier(&463u32));
+
+    params.hash = Symbol::new(&ts.env, "blake2b");
+    assert_error(
+        &ts.client.try_compute_target_variant(&params),
+        Error::UnsupportedHash,
+    );
+}
+
+// ============================================================================
+// Claim Required
+// ============================================================================
+
+#[test]
+fn test_winner_claims_to_settle() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_claim_required(&true);
+    start_resolvable(&ts, 470);
+
+    assert_eq!(ts.client.resolve_game(&470u32), Outcome::Player1Won);
+    assert!(ts.client.get_game(&470u32).awaiting_claim);
+    assert_eq!(hub.ended().len(), 0);
+
+    ts.client.claim_winnings(&470u32);
+    assert_eq!(
+        ts.env.auths()[0].0,
+        ts.player1,
+        "the winner must authorize the claim"
+    );
+    assert_eq!(hub.ended(), vec![&ts.env, (470u32, 0u32)]);
+    assert!(!ts.client.get_game(&470u32).awaiting_claim);
+
+    assert_error(
+        &ts.client.try_claim_winnings(&470u32),
+        Error::NothingToClaim,
+    );
+}
+
+#[test]
+fn test_unclaimed_winnings_swept_after_window() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_claim_required(&true);
+    ts.client.set_claim_window(&100);
+    start_resolvable(&ts, 471);
+    ts.client.resolve_game(&471u32);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 99);
+    assert_error(
+        &ts.client.try_sweep_unclaimed(&471u32),
+        Error::ClaimWindowOpen,
+    );
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 1);
+    ts.client.sweep_unclaimed(&471u32);
+    assert_eq!(hub.ended(), vec![&ts.env, (471u32, 0u32)]);
+    assert_error(
+        &ts.client.try_claim_winnings(&471u32),
+        Error::NothingToClaim,
+    );
+}
+
+#[test]
+fn test_claim_not_needed_when_disabled() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 472);
+    ts.client.resolve_game(&472u32);
+    assert_eq!(hub.ended().len(), 1);
+    assert_error(
+        &ts.client.try_claim_winnings(&472u32),
+        Error::NothingToClaim,
+    );
+}
+
+// ============================================================================
+// Lobby View
+// ============================================================================
+
+#[test]
+fn test_lobby_view_mixes_present_and_absent_sessions() {
+    let ts = setup();
+    let hash = start(&ts, 480);
+    start_resolvable(&ts, 481);
+    start_resolvable(&ts, 482);
+    ts.client.resolve_game(&482u32);
+
+    let view = ts
+        .client
+        .get_lobby_view(&vec![&ts.env, 480u32, 999u32, 481u32, 482u32]);
+    let entry = |phase| {
+        Some(LobbyEntry {
+            phase,
+            treasure_hash: hash.clone(),
+            player1: ts.player1.clone(),
+            player2: ts.player2.clone(),
+        })
+    };
+    assert_eq!(
+        view,
+        vec![
+            &ts.env,
+            entry(GamePhase::Open),
+            None,
+            entry(GamePhase::OneVerified),
+            entry(GamePhase::Resolved),
+        ]
+    );
+}
+
+#[test]
+fn test_lobby_view_rejects_oversized_batch() {
+    let ts = setup();
+    let mut ids = Vec::new(&ts.env);
+    for id in 0..51u32 {
+        ids.push_back(id);
+    }
+    assert_error(&ts.client.try_get_lobby_view(&ids), Error::BatchTooLarge);
+    ids.pop_back();
+    assert_eq!(ts.client.get_lobby_view(&ids).len(), 50);
+}
+
+// ============================================================================
+// Reclaim
+// ============================================================================
+
+#[test]
+fn test_reclaim_drops_outcome_by_default() {
+    let ts = setup();
+    start_resolvable(&ts, 490);
+    assert_error(&ts.client.try_reclaim_game(&490u32), Error::GameNotResolved);
+    ts.client.resolve_game(&490u32);
+
+    ts.client.reclaim_game(&490u32);
+    assert_error(&ts.client.try_get_game(&490u32), Error::GameNotFound);
+    assert_error(&ts.client.try_get_outcome(&490u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_reclaim_keeps_outcome_when_enabled() {
+    let ts = setup();
+    ts.client.set_keep_outcome_after_reclaim(&true);
+    start_resolvable(&ts, 491);
+    ts.client.resolve_game(&491u32);
+
+    ts.client.reclaim_game(&491u32);
+    assert_error(&ts.client.try_get_game(&491u32), Error::GameNotFound);
+    assert_eq!(ts.client.get_outcome(&491u32), Outcome::Player1Won);
+}
+
+#[test]
+fn test_reclaim_refused_while_claim_pending() {
+    let ts = setup();
+    ts.client.set_claim_required(&true);
+    start_resolvable(&ts, 492);
+    ts.client.resolve_game(&492u32);
+    assert_error(&ts.client.try_reclaim_game(&492u32), Error::ClaimPending);
+
+    ts.client.claim_winnings(&492u32);
+    ts.client.reclaim_game(&492u32);
+}
+
+#[test]
+fn test_close_session_deletes_resolved_game() {
+    let ts = setup();
+    start_resolvable(&ts, 950);
+    assert_error(
+        &ts.client.try_close_session(&950u32),
+        Error::GameNotResolved,
+    );
+    ts.client.resolve_game(&950u32);
+
+    ts.client.close_session(&950u32);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (symbol_short!("game"), symbol_short!("closed"), 950u32).into_val(&ts.env),
+                Outcome::Player1Won.into_val(&ts.env),
+            ),
+        ]
+    );
+    assert_error(&ts.client.try_get_game(&950u32), Error::GameNotFound);
+    assert_error(&ts.client.try_close_session(&950u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_close_session_waits_for_escalation_window() {
+    let ts = setup();
+    ts.client.set_escalation_window(&10);
+    start_arbitrated(&ts, 951);
+    ts.client.resolve_game(&951u32);
+    assert_error(&ts.client.try_close_session(&951u32), Error::EscalationOpen);
+
+    ts.env.ledger().with_mut(|l| l.sequence_number += 10);
+    ts.client.close_session(&951u32);
+    assert_error(&ts.client.try_get_game(&951u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_close_session_waits_for_reuse_cooldown() {
+    let ts = setup();
+    ts.client.set_session_reuse_cooldown(&10);
+    start_resolvable(&ts, 960);
+    ts.client.resolve_game(&960u32);
+    assert_error(
+        &ts.client.try_close_session(&960u32),
+        Error::SessionReuseTooSoon,
+    );
+    assert_error(
+        &ts.client.try_start_game(
+            &960u32,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &test_treasure_hash(&ts.env),
+        ),
+        Error::SessionReuseTooSoon,
+    );
+
+    ts.env.ledger().with_mut(|l| l.sequence_number += 10);
+    ts.client.close_session(&960u32);
+    start(&ts, 960);
+}
+
+// ============================================================================
+// Effective Deadline
+// ============================================================================
+
+#[test]
+fn test_effective_deadline_defaults_to_entry_expiry() {
+    let ts = setup();
+    let seq = ts.env.ledger().sequence();
+    start(&ts, 500);
+    assert_eq!(ts.client.effective_deadline(&500u32), seq + 518_400 + 1);
+}
+
+#[test]
+fn test_effective_deadline_uses_shorter_time_limit() {
+    let ts = setup();
+    let seq = ts.env.ledger().sequence();
+    let options = GameOptions {
+        time_limit_seconds: Some(600),
+        ..GameOptions::default()
+    };
+    start_with(&ts, 501, &options);
+    assert_eq!(ts.client.effective_deadline(&501u32), seq + 121);
+
+    // A time limit beyond the entry's TTL does not extend the deadline.
+    let options = GameOptions {
+        time_limit_seconds: Some(90 * 24 * 60 * 60),
+        ..GameOptions::default()
+    };
+    start_with(&ts, 502, &options);
+    assert_eq!(ts.client.effective_deadline(&502u32), seq + 518_400 + 1);
+
+    ts.env.ledger().with_mut(|li| {
+        li.timestamp += 601;
+        li.sequence_number += 130;
+    });
+    assert_eq!(ts.client.effective_deadline(&501u32), seq + 130);
+}
+
+#[test]
+fn test_effective_deadline_after_submissions_and_resolution() {
+    let ts = setup();
+    ts.client.set_proportional_ttl(&true);
+    let seq = ts.env.ledger().sequence();
+    start_resolvable(&ts, 503);
+    // A proportional extension never shortens the entry's lifetime.
+    assert_eq!(ts.client.effective_deadline(&503u32), seq + 518_400 + 1);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 10);
+    ts.client.resolve_game(&503u32);
+    assert_eq!(ts.client.effective_deadline(&503u32), seq + 10);
+
+    ts.client.set_max_submissions(&1);
+    start_resolvable(&ts, 504);
+    assert_eq!(ts.client.effective_deadline(&504u32), seq + 10);
+}
+
+#[test]
+fn test_session_ttl_decreases_and_extends() {
+    let ts = setup();
+    assert_error(&ts.client.try_get_session_ttl(&953u32), Error::GameNotFound);
+    start(&ts, 953);
+    assert_eq!(ts.client.get_session_ttl(&953u32), 518_400);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    assert_eq!(ts.client.get_session_ttl(&953u32), 517_400);
+
+    let outsider = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_extend_session(&953u32, &outsider),
+        Error::NotPlayer,
+    );
+    ts.client.extend_session(&953u32, &ts.player2);
+    assert_eq!(ts.client.get_session_ttl(&953u32), 518_400);
+}
+
+#[test]
+fn test_game_ttl_is_configurable() {
+    let ts = setup();
+    assert_eq!(ts.client.get_game_ttl(), 518_400);
+    assert_error(&ts.client.try_set_game_ttl(&17_279u32), Error::InvalidTtl);
+    assert_error(&ts.client.try_set_game_ttl(&u32::MAX), Error::InvalidTtl);
+
+    ts.client.set_game_ttl(&100_000u32);
+    assert_eq!(ts.client.get_game_ttl(), 100_000);
+    start(&ts, 954);
+    assert_eq!(ts.client.get_session_ttl(&954u32), 100_000);
+    ts.env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    ts.client.extend_session(&954u32, &ts.player1);
+    assert_eq!(ts.client.get_session_ttl(&954u32), 100_000);
+}
+
+#[test]
+fn test_set_game_ttl_is_admin_only() {
+    let ts = setup();
+    let outsider = Address::generate(&ts.env);
+    mock_single_auth(
+        &ts,
+        &outsider,
+        "set_game_ttl",
+        (100_000u32,).into_val(&ts.env),
+    );
+    assert!(ts.client.try_set_game_ttl(&100_000u32).is_err());
+    assert_eq!(ts.client.get_game_ttl(), 518_400);
+}
+
+// ============================================================================
+// Preregistered Targets
+// ============================================================================
+
+#[test]
+fn test_start_game_adopts_preregistered_target() {
+    let ts = setup();
+    let target = BytesN::from_array(&ts.env, &[0x5au8; 32]);
+    ts.client.preregister_target(&510u32, &target);
+
+    start(&ts, 510);
+    assert_eq!(ts.client.get_treasure_hash(&510u32), target);
+
+    // The pre-registration is consumed by the start.
+    start(&ts, 511);
+    assert_eq!(
+        ts.client.get_treasure_hash(&511u32),
+        test_treasure_hash(&ts.env)
+    );
+}
+
+#[test]
+fn test_start_game_rejects_live_session() {
+    let ts = setup();
+    let hash = start(&ts, 1);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&1u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    let other = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_start_game(
+            &1u32,
+            &ts.player1,
+            &other,
+            &POINTS,
+            &POINTS,
+            &BytesN::from_array(&ts.env, &[0x5au8; 32]),
+        ),
+        Error::SessionExists,
+    );
+
+    let game = ts.client.get_game(&1u32);
+    assert_eq!(game.player2, ts.player2);
+    assert_eq!(game.treasure_hash, hash);
+    assert_eq!(game.player1_energy, Some(10));
+}
+
+#[test]
+fn test_preregister_rejects_started_session() {
+    let ts = setup();
+    start(&ts, 512);
+    let target = BytesN::from_array(&ts.env, &[0x5au8; 32]);
+    assert_error(
+        &ts.client.try_preregister_target(&512u32, &target),
+        Error::SessionExists,
+    );
+}
+
+// ============================================================================
+// Distinct Proofs
+// ============================================================================
+
+#[test]
+fn test_distinct_proofs_rejects_copied_proof() {
+    let ts = setup();
+    ts.client.set_require_distinct_proofs(&true);
+    start_resolvable(&ts, 520);
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&520u32));
+
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&520u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32),
+        Error::DuplicateProof,
+    );
+    let other = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+    ts.client
+        .submit_zk_proof(&520u32, &ts.player2, &other, &pi, &10u32);
+    assert_eq!(ts.client.get_game(&520u32).submissions, 2);
+}
+
+#[test]
+fn test_identical_proofs_allowed_by_default() {
+    let ts = setup();
+    start_resolvable(&ts, 521);
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&521u32));
+    ts.client
+        .submit_zk_proof(&521u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(ts.client.get_game(&521u32).submissions, 2);
+}
+
+// ============================================================================
+// Resolution Time
+// ============================================================================
+
+#[test]
+fn test_resolution_time_matches_resolving_ledger() {
+    let ts = setup();
+    start_resolvable(&ts, 530);
+    assert_error(
+        &ts.client.try_get_resolution_time(&530u32),
+        Error::GameNotResolved,
+    );
+
+    ts.env.ledger().with_mut(|li| {
+        li.sequence_number += 42;
+        li.timestamp += 210;
+    });
+    ts.client.resolve_game(&530u32);
+    let ledger = ts.env.ledger().sequence();
+    let timestamp = ts.env.ledger().timestamp();
+
+    ts.env.ledger().with_mut(|li| {
+        li.sequence_number += 1;
+        li.timestamp += 5;
+    });
+    assert_eq!(ts.client.get_resolution_time(&530u32), (ledger, timestamp));
+}
+
+#[test]
+fn test_is_proof_used_before_and_after_submission() {
+    let ts = setup();
+    start(&ts, 522);
+    let proof_hash: BytesN<32> = ts.env.crypto().keccak256(&valid_proof(&ts.env)).into();
+    assert!(!ts.client.is_proof_used(&522u32, &proof_hash));
+
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&522u32));
+    ts.client
+        .submit_zk_proof(&522u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert!(ts.client.is_proof_used(&522u32, &proof_hash));
+
+    let other: BytesN<32> = ts.env.crypto().keccak256(&invalid_proof(&ts.env)).into();
+    assert!(!ts.client.is_proof_used(&522u32, &other));
+    assert_error(
+        &ts.client.try_is_proof_used(&999u32, &proof_hash),
+        Error::GameNotFound,
+    );
+}
+
+// ============================================================================
+// Standalone (no Game Hub)
+// ============================================================================
+
+#[test]
+fn test_standalone_full_lifecycle() {
+    let ts = setup_with_hub(false);
+    assert!(ts.client.is_operational());
+    assert_error(&ts.client.try_get_hub(), Error::NoHubConfigured);
+
+    let hash = start(&ts, 540);
+    assert_eq!(ts.client.get_game(&540u32).hub, None);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&540u32, &ts.player1, &valid_proof(&ts.env), &pi, &30u32);
+    let other = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+    ts.client
+        .submit_zk_proof(&540u32, &ts.player2, &other, &pi, &20u32);
+
+    assert_eq!(ts.client.resolve_game(&540u32), Outcome::Player2Won);
+    assert_eq!(ts.client.get_winner(&540u32), Some(ts.player2.clone()));
+    assert_eq!(ts.client.phase_counts().resolved, 1);
+}
+
+#[test]
+fn test_standalone_claim_settles_without_hub() {
+    let ts = setup_with_hub(false);
+    ts.client.set_claim_required(&true);
+    start_resolvable(&ts, 541);
+    ts.client.resolve_game(&541u32);
+    ts.client.claim_winnings(&541u32);
+    assert!(!ts.client.get_game(&541u32).awaiting_claim);
+}
+
+// ============================================================================
+// Can Still Win
+// ============================================================================
+
+#[test]
+fn test_can_still_win_after_opponent_verifies() {
+    let ts = setup();
+    let hash = start(&ts, 550);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    assert!(ts.client.can_still_win(&550u32, &ts.player2));
+
+    ts.client
+        .submit_zk_proof(&550u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    // Player 2 can still come back with lower energy.
+    assert!(ts.client.can_still_win(&550u32, &ts.player2));
+    assert!(ts.client.can_still_win(&550u32, &ts.player1));
+
+    let other = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+    ts.client
+        .submit_zk_proof(&550u32, &ts.player2, &other, &pi, &20u32);
+    assert!(!ts.client.can_still_win(&550u32, &ts.player2));
+    assert!(ts.client.can_still_win(&550u32, &ts.player1));
+
+    assert_error(
+        &ts.client
+            .try_can_still_win(&550u32, &Address::generate(&ts.env)),
+        Error::NotPlayer,
+    );
+}
+
+#[test]
+fn test_cannot_win_against_zero_energy_or_closed_submissions() {
+    let ts = setup();
+    let hash = start(&ts, 551);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&551u32, &ts.player1, &valid_proof(&ts.env), &pi, &0u32);
+    // Ties go to player 1 by default, so nothing beats zero energy.
+    assert!(!ts.client.can_still_win(&551u32, &ts.player2));
+
+    ts.client.set_max_submissions(&1);
+    start_resolvable(&ts, 552);
+    assert!(!ts.client.can_still_win(&552u32, &ts.player2));
+
+    start_resolvable(&ts, 553);
+    ts.client.resolve_game(&553u32);
+    assert!(ts.client.can_still_win(&553u32, &ts.player1));
+    assert!(!ts.client.can_still_win(&553u32, &ts.player2));
+}
+
+// ============================================================================
+// Rekey
+// ============================================================================
+
+#[test]
+fn test_rekey_fresh_game() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    let name = Bytes::from_slice(&ts.env, b"collided");
+    ts.client.start_game_named(
+        &name,
+        &560u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &POINTS,
+        &test_treasure_hash(&ts.env),
+    );
+
+    let target = BytesN::from_array(&ts.env, &[0x61u8; 32]);
+    ts.client.rekey_game(&560u32, &561u32, &target);
+
+    assert_error(&ts.client.try_get_game(&560u32), Error::GameNotFound);
+    let game = ts.client.get_game(&561u32);
+    assert_eq!(game.treasure_hash, target);
+    assert_eq!(
+        game.nullifier,
+        ts.client
+            .derive_nullifier(&561u32, &ts.player1, &ts.player2, &None)
+    );
+    assert_eq!(ts.client.resolve_session_name(&name), 561u32);
+    assert_eq!(
+        ts.client.get_player_sessions(&ts.player1),
+        vec![&ts.env, 561u32]
+    );
+
+    // The hub still settles the game under the id it locked points for.
+    let pi = treasure_hash_as_bytes(&ts.env, &target);
+    ts.client
+        .submit_zk_proof(&561u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&561u32);
+    assert_eq!(hub.ended(), vec![&ts.env, (560u32, 0u32)]);
+}
+
+#[test]
+fn test_rekeyed_game_holds_its_old_id() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start(&ts, 565);
+    ts.client.set_game_max_points(&565u32, &(POINTS / 2));
+    let target = BytesN::from_array(&ts.env, &[0x61u8; 32]);
+    ts.client.rekey_game(&565u32, &566u32, &target);
+
+    // The hub still settles 566 as 565, so 565 stays taken.
+    let hash = test_treasure_hash(&ts.env);
+    assert_error(
+        &ts.client
+            .try_start_game(&565u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &hash),
+        Error::SessionExists,
+    );
+    start(&ts, 567);
+    assert_error(
+        &ts.client.try_rekey_game(&567u32, &565u32, &target),
+        Error::SessionExists,
+    );
+
+    ts.client.cancel_game(&566u32, &None);
+    assert_eq!(hub.cancelled(), vec![&ts.env, 565u32]);
+    // The cap granted for the moved game does not carry over.
+    start(&ts, 565);
+}
+
+#[test]
+fn test_rekey_rejects_occupied_id_and_submitted_game() {
+    let ts = setup();
+    start(&ts, 562);
+    start_resolvable(&ts, 563);
+    let target = BytesN::from_array(&ts.env, &[0x61u8; 32]);
+
+    assert_error(
+        &ts.client.try_rekey_game(&562u32, &563u32, &target),
+        Error::SessionExists,
+    );
+    assert_error(
+        &ts.client.try_rekey_game(&563u32, &564u32, &target),
+        Error::SubmissionsRecorded,
+    );
+}
+
+// ============================================================================
+// Idempotent Submit
+// ============================================================================
+
+#[test]
+fn test_idempotent_submit_accepts_exact_retry() {
+    let ts = setup();
+    ts.client.set_idempotent_submit(&true);
+    start_resolvable(&ts, 570);
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&570u32));
+
+    assert_eq!(
+        ts.client
+            .submit_zk_proof(&570u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32),
+        None
+    );
+    let game = ts.client.get_game(&570u32);
+    assert_eq!(game.submissions, 1);
+    assert_eq!(game.player1_energy, Some(10));
+}
+
+#[test]
+fn test_idempotent_submit_rejects_mismatching_retry() {
+    let ts = setup();
+    ts.client.set_idempotent_submit(&true);
+    start_resolvable(&ts, 571);
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&571u32));
+    let other = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&571u32, &ts.player1, &other, &pi, &10u32),
+        Error::AlreadySubmitted,
+    );
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&571u32, &ts.player1, &valid_proof(&ts.env), &pi, &9u32),
+        Error::AlreadySubmitted,
+    );
+}
+
+#[test]
+fn test_retry_rejected_without_idempotent_submit() {
+    let ts = setup();
+    start_resolvable(&ts, 572);
+    let pi = treasure_hash_as_bytes(&ts.env, &ts.client.get_treasure_hash(&572u32));
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&572u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32),
+        Error::AlreadySubmitted,
+    );
+}
+
+// ============================================================================
+// Storage Report
+// ============================================================================
+
+#[test]
+fn test_storage_report_tracks_started_and_resolved_games() {
+    let ts = setup();
+    start(&ts, 580);
+    start_resolvable(&ts, 581);
+    start_resolvable(&ts, 582);
+    ts.client.resolve_game(&582u32);
+
+    assert_eq!(
+        ts.client.storage_report(),
+        StorageReport {
+            active_games: 2,
+            resolved_games: 1,
+            active_index_len: 2,
+            session_names: 0,
+            estimated_temporary_entries: 3,
+        }
+    );
+}
+
+// ============================================================================
+// Game Mode
+// ============================================================================
+
+#[test]
+fn test_default_game_mode_applies_to_plain_start() {
+    let ts = setup();
+    start(&ts, 590);
+    assert_eq!(ts.client.get_game(&590u32).mode, GameMode::LowestEnergy);
+
+    ts.client.set_default_game_mode(&GameMode::FirstWins);
+    let hash = start(&ts, 591);
+    assert_eq!(ts.client.get_game(&591u32).mode, GameMode::FirstWins);
+
+    // The first verified proof wins and resolves the game at once.
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    assert_eq!(
+        ts.client
+            .submit_zk_proof(&591u32, &ts.player2, &valid_proof(&ts.env), &pi, &50u32),
+        Some(Outcome::Player2Won)
+    );
+    assert!(!ts.client.can_still_win(&591u32, &ts.player1));
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&591u32, &ts.player1, &valid_proof(&ts.env), &pi, &1u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_explicit_game_mode_overrides_default() {
+    let ts = setup();
+    ts.client.set_default_game_mode(&GameMode::FirstWins);
+    let options = GameOptions {
+        mode: GameMode::LowestEnergy,
+        ..GameOptions::default()
+    };
+    start_with(&ts, 592, &options);
+    assert_eq!(ts.client.get_game(&592u32).mode, GameMode::LowestEnergy);
+
+    start_with(&ts, 593, &GameOptions::default());
+    assert_eq!(ts.client.get_game(&593u32).mode, GameMode::FirstWins);
+}
+
+// ============================================================================
+// Admin Refund
+// ============================================================================
+
+#[test]
+fn test_admin_refund_partially_submitted_game() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 600);
+
+    let admin = ts.client.get_admin();
+    ts.client.admin_refund(&600u32);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (Symbol::new(&ts.env, "admin_refund"), 600u32).into_val(&ts.env),
+                admin.into_val(&ts.env),
+            ),
+        ]
+    );
+    assert_eq!(hub.refunded(), vec![&ts.env, 600u32]);
+    assert_eq!(hub.ended().len(), 0);
+    assert_eq!(ts.client.get_outcome(&600u32), Outcome::NeitherFound);
+    assert_eq!(ts.client.get_winner(&600u32), None);
+    assert_eq!(ts.client.active_session_count(), 0);
+    assert_eq!(ts.client.phase_counts(), counts(0, 0, 0, 1));
+
+    assert_error(
+        &ts.client.try_admin_refund(&600u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+// ============================================================================
+// Hub Report Preview
+// ============================================================================
+
+/// Start a game, submit the given energies, and return the previewed code
+/// after checking it matches what the hub receives on resolution.
+fn preview_and_resolve(
+    ts: &TestSetup,
+    session_id: u32,
+    p1_energy: Option<u32>,
+    p2_energy: Option<u32>,
+) -> u32 {
+    let hub = use_recording_hub(ts);
+    let hash = start(ts, session_id);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    if let Some(energy) = p1_energy {
+        ts.client.submit_zk_proof(
+            &session_id,
+            &ts.player1,
+            &valid_proof(&ts.env),
+            &pi,
+            &energy,
+        );
+    }
+    if let Some(energy) = p2_energy {
+        let proof = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+        ts.client
+            .submit_zk_proof(&session_id, &ts.player2, &proof, &pi, &energy);
+    }
+
+    let preview = ts.client.preview_hub_report(&session_id);
+    ts.client.resolve_game(&session_id);
+    assert_eq!(hub.ended(), vec![&ts.env, (session_id, preview)]);
+    preview
+}
+
+#[test]
+fn test_preview_hub_report_across_outcomes() {
+    let ts = setup();
+    assert_eq!(preview_and_resolve(&ts, 610, Some(10), None), 0);
+    assert_eq!(preview_and_resolve(&ts, 611, None, Some(10)), 1);
+    assert_eq!(preview_and_resolve(&ts, 612, Some(30), Some(20)), 1);
+    assert_eq!(preview_and_resolve(&ts, 613, Some(20), Some(30)), 0);
+}
+
+#[test]
+fn test_preview_hub_report_ties_are_draws() {
+    let ts = setup();
+    assert_eq!(preview_and_resolve(&ts, 614, Some(20), Some(20)), 2);
+    ts.client.set_tie_default(&TieDefault::Player2);
+    assert_eq!(preview_and_resolve(&ts, 615, Some(20), Some(20)), 2);
+    ts.client.set_tie_default(&TieDefault::Draw);
+    assert_eq!(preview_and_resolve(&ts, 616, Some(20), Some(20)), 2);
+}
+
+#[test]
+fn test_preview_hub_report_errors() {
+    let ts = setup();
+    start(&ts, 617);
+    assert_error(
+        &ts.client.try_preview_hub_report(&617u32),
+        Error::NeitherPlayerSubmitted,
+    );
+    start_resolvable(&ts, 618);
+    ts.client.resolve_game(&618u32);
+    assert_error(
+        &ts.client.try_preview_hub_report(&618u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+// ============================================================================
+// Named Verifiers
+// ============================================================================
+
+#[test]
+fn test_register_verifier_enforces_cap() {
+    let ts = setup();
+    ts.client.set_max_verifiers(&2);
+    let a = ts.env.register(MockVerifier, ());
+    let b = ts.env.register(MockVerifier, ());
+    let c = ts.env.register(MockVerifier, ());
+    ts.client.register_verifier(&symbol_short!("v1"), &a);
+    ts.client.register_verifier(&symbol_short!("v2"), &b);
+
+    assert_error(
+        &ts.client.try_register_verifier(&symbol_short!("v3"), &c),
+        Error::TooManyVerifiers,
+    );
+    // Replacing an existing name does not take a new slot.
+    ts.client.register_verifier(&symbol_short!("v2"), &c);
+    assert_eq!(
+        ts.client.get_named_verifier(&symbol_short!("v2")),
+        Some(c.clone())
+    );
+
+    ts.client.unregister_verifier(&symbol_short!("v1"));
+    ts.client.register_verifier(&symbol_short!("v3"), &c);
+    assert_eq!(ts.client.get_named_verifier(&symbol_short!("v1")), None);
+}
+
+#[test]
+fn test_unregister_rejects_verifier_with_live_games() {
+    let ts = setup();
+    let verifier = ts.env.register(MockVerifier, ());
+    ts.client
+        .register_verifier(&symbol_short!("ranked"), &verifier);
+    let options = GameOptions {
+        verifier: Some(symbol_short!("ranked")),
+        ..GameOptions::default()
+    };
+    let hash = start_with(&ts, 620, &options);
+
+    assert_error(
+        &ts.client.try_unregister_verifier(&symbol_short!("ranked")),
+        Error::VerifierInUse,
+    );
+
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&620u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(
+        ts.client.games_verified_by(&verifier),
+        vec![&ts.env, 620u32]
+    );
+    ts.client.resolve_game(&620u32);
+    ts.client.unregister_verifier(&symbol_short!("ranked"));
+
+    assert_error(
+        &ts.client.try_start_game_with_options(
+            &621u32,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &hash,
+            &options,
+        ),
+        Error::UnknownVerifier,
+    );
+}
+
+// ============================================================================
+// Start Auth Preview
+// ============================================================================
+
+#[test]
+fn test_start_game_auth_preview_matches_actual_auths() {
+    let ts = setup();
+    let preview =
+        ts.client
+            .start_game_auth_preview(&630u32, &ts.player1, &ts.player2, &POINTS, &250i128);
+    assert_eq!(
+        preview,
+        vec![
+            &ts.env,
+            AuthRequirement {
+                address: ts.player1.clone(),
+                args: vec![&ts.env, 630u32.into_val(&ts.env), POINTS.into_val(&ts.env)],
+            },
+            AuthRequirement {
+                address: ts.player2.clone(),
+                args: vec![&ts.env, 630u32.into_val(&ts.env), 250i128.into_val(&ts.env)],
+            },
+        ]
+    );
+
+    ts.client.start_game(
+        &630u32,
+        &ts.player1,
+        &ts.player2,
+        &POINTS,
+        &250i128,
+        &test_treasure_hash(&ts.env),
+    );
+    let auths = ts.env.auths();
+    assert_eq!(auths.len(), preview.len() as usize);
+    for (requirement, (address, invocation)) in preview.iter().zip(auths) {
+        assert_eq!(requirement.address, address);
+        assert_eq!(
+            invocation.function,
+            AuthorizedFunction::Contract((
+                ts.client.address.clone(),
+                Symbol::new(&ts.env, "start_game"),
+                requirement.args,
+            ))
+        );
+    }
+}
+
+#[test]
+fn test_resolve_auth_preview_empty_when_permissionless() {
+    let ts = setup();
+    start_resolvable(&ts, 635);
+    let keeper = Address::generate(&ts.env);
+    assert_eq!(
+        ts.client.resolve_auth_preview(&635u32, &keeper),
+        Vec::new(&ts.env)
+    );
+    ts.client.resolve_game(&635u32);
+}
+
+#[test]
+fn test_resolve_auth_preview_player_gated() {
+    let ts = setup();
+    ts.client.set_players_only_resolve(&true);
+    start_resolvable(&ts, 636);
+
+    let keeper = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_resolve_auth_preview(&636u32, &keeper),
+        Error::ResolutionGated,
+    );
+    assert_error(&ts.client.try_resolve_game(&636u32), Error::ResolutionGated);
+    assert_error(
+        &ts.client.try_resolve_game_as(&636u32, &keeper),
+        Error::ResolutionGated,
+    );
+
+    let preview = ts.client.resolve_auth_preview(&636u32, &ts.player2);
+    ts.client.resolve_game_as(&636u32, &ts.player2);
+    let auths = ts.env.auths();
+    assert_eq!(auths.len(), preview.len() as usize);
+    for (requirement, (address, invocation)) in preview.iter().zip(auths) {
+        assert_eq!(requirement.address, address);
+        assert_eq!(
+            invocation.function,
+            AuthorizedFunction::Contract((
+                ts.client.address.clone(),
+                Symbol::new(&ts.env, "resolve_game_as"),
+                requirement.args,
+            ))
+        );
+    }
+}
+
+// ============================================================================
+// Active Pair Index
+// ============================================================================
+
+#[test]
+fn test_active_game_between_tracks_start_and_resolve() {
+    let ts = setup();
+    assert_eq!(
+        ts.client.active_game_between(&ts.player1, &ts.player2),
+        None
+    );
+
+    start_resolvable(&ts, 640);
+    assert_eq!(
+        ts.client.active_game_between(&ts.player1, &ts.player2),
+        Some(640)
+    );
+    assert_eq!(
+        ts.client.active_game_between(&ts.player2, &ts.player1),
+        Some(640)
+    );
+    let stranger = Address::generate(&ts.env);
+    assert_eq!(ts.client.active_game_between(&ts.player1, &stranger), None);
+
+    ts.client.resolve_game(&640u32);
+    assert_eq!(
+        ts.client.active_game_between(&ts.player2, &ts.player1),
+        None
+    );
+}
+
+// ============================================================================
+// Rounding Policy
+// ============================================================================
+
+#[test]
+fn test_split_points_conserves_under_each_policy() {
+    let ts = setup();
+    let amounts = [0i128, 1, 7, 9_999, 10_001, 123_457, MAX_POINTS_CEILING + 3];
+    for policy in [RoundingPolicy::ToProtocol, RoundingPolicy::ToWinner] {
+        ts.client.set_rounding_policy(&policy);
+        for amount in amounts {
+            for bps in [0u32, 1, 3_333, 9_750, 10_000] {
+                let split = ts.client.split_points(&amount, &bps);
+                assert_eq!(split.winner + split.protocol, amount);
+                assert!(split.winner >= 0 && split.protocol >= 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_split_points_remainder_follows_policy() {
+    let ts = setup();
+    // 101 × 33.33% = 33.6633
+    assert_eq!(
+        ts.client.split_points(&101, &3_333),
+        PointsSplit {
+            winner: 33,
+            protocol: 68,
+        }
+    );
+    ts.client.set_rounding_policy(&RoundingPolicy::ToWinner);
+    assert_eq!(
+        ts.client.get_fee_config().rounding_policy,
+        RoundingPolicy::ToWinner
+    );
+    assert_eq!(
+        ts.client.split_points(&101, &3_333),
+        PointsSplit {
+            winner: 34,
+            protocol: 67,
+        }
+    );
+
+    assert_error(
+        &ts.client.try_split_points(&-1, &5_000),
+        Error::InvalidSplit,
+    );
+    assert_error(
+        &ts.client.try_split_points(&100, &10_001),
+        Error::InvalidSplit,
+    );
+}
+
+// ============================================================================
+// Submission Diagnostics
+// ============================================================================
+
+#[test]
+fn test_submission_diagnostics_reports_every_failure() {
+    let ts = setup();
+    ts.client.set_require_distinct_proofs(&true);
+    ts.client.set_max_submissions(&1);
+    let options = GameOptions {
+        time_limit_seconds: Some(60),
+        ..GameOptions::default()
+    };
+    let hash = start_with(&ts, 650, &options);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&650u32, &ts.player2, &proof, &pi),
+        Vec::<Error>::new(&ts.env)
+    );
+
+    ts.client
+        .submit_zk_proof(&650u32, &ts.player1, &proof, &pi, &10u32);
+    ts.env.ledger().with_mut(|li| li.timestamp += 61);
+    let wrong_pi = Bytes::from_array(&ts.env, &[0u8; 32]);
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&650u32, &ts.player2, &proof, &wrong_pi),
+        vec![
+            &ts.env,
+            Error::SubmissionBudgetExhausted,
+            Error::TimeLimitExceeded,
+            Error::PublicInputMismatch,
+            Error::DuplicateProof,
+        ]
+    );
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&650u32, &ts.player1, &proof, &pi),
+        vec![
+            &ts.env,
+            Error::AlreadySubmitted,
+            Error::SubmissionBudgetExhausted,
+            Error::TimeLimitExceeded,
+            Error::DuplicateProof,
+        ]
+    );
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&999u32, &ts.player1, &proof, &pi),
+        vec![&ts.env, Error::GameNotFound]
+    );
+}
+
+// ============================================================================
+// Frozen Resolved Games
+// ============================================================================
+
+#[test]
+fn test_frozen_resolved_game_rejects_every_mutation() {
+    let ts = setup();
+    ts.client.set_frozen_resolved(&true);
+    let hash = start(&ts, 660);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&660u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&660u32);
+
+    let other = Bytes::from_array(&ts.env, &[0x02u8; 64]);
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&660u32, &ts.player2, &other, &pi, &10u32),
+        Error::GameFrozen,
+    );
+    assert_error(
+        &ts.client
+            .try_submit_proof_chunk(&660u32, &ts.player2, &0u32, &other, &1u32),
+        Error::GameFrozen,
+    );
+    assert_error(&ts.client.try_resolve_game(&660u32), Error::GameFrozen);
+    assert_error(
+        &ts.client.try_force_resolve(&660u32, &None),
+        Error::GameFrozen,
+    );
+    assert_error(&ts.client.try_admin_refund(&660u32), Error::GameFrozen);
+    assert_error(
+        &ts.client.try_cancel_game(&660u32, &None),
+        Error::GameFrozen,
+    );
+    assert_error(&ts.client.try_claim_timeout(&660u32), Error::GameFrozen);
+    assert_error(
+        &ts.client.try_extend_session(&660u32, &ts.player1),
+        Error::GameFrozen,
+    );
+
+    // Reads still work, and reclaim ends the frozen state.
+    assert_eq!(ts.client.get_outcome(&660u32), Outcome::Player1Won);
+    ts.client.reclaim_game(&660u32);
+    assert_error(&ts.client.try_get_game(&660u32), Error::GameNotFound);
+}
+
+#[test]
+fn test_resolved_game_not_frozen_by_default() {
+    let ts = setup();
+    start_resolvable(&ts, 661);
+    ts.client.resolve_game(&661u32);
+    assert_eq!(ts.client.resolve_game(&661u32), Outcome::Player1Won);
+    assert_error(
+        &ts.client.try_force_resolve(&661u32, &None),
+        Error::GameAlreadyResolved,
+    );
+}
+
+// ============================================================================
+// Matchmaking Queue
+// ============================================================================
+
+#[test]
+fn test_join_queue_starts_game_for_two_players() {
+    let ts = setup();
+    let target = BytesN::from_array(&ts.env, &[0x5au8; 32]);
+    assert_eq!(ts.client.next_queue_session(), QUEUE_SESSION_BASE);
+    ts.client.preregister_target(&QUEUE_SESSION_BASE, &target);
+
+    assert_eq!(ts.client.join_queue(&ts.player1, &POINTS), None);
+    assert_eq!(ts.client.queue_length(), 1);
+    assert_eq!(
+        ts.client.join_queue(&ts.player2, &POINTS),
+        Some(QUEUE_SESSION_BASE)
+    );
+    assert_eq!(ts.client.queue_length(), 0);
+    assert_eq!(ts.client.next_queue_session(), QUEUE_SESSION_BASE + 1);
+
+    let game = ts.client.get_game(&QUEUE_SESSION_BASE);
+    assert_eq!(game.player1, ts.player1);
+    assert_eq!(game.player2, ts.player2);
+    assert_eq!(ts.client.get_treasure_hash(&QUEUE_SESSION_BASE), target);
+}
+
+#[test]
+fn test_queue_session_ids_are_reserved_for_join_queue() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    assert_error(
+        &ts.client.try_start_game(
+            &QUEUE_SESSION_BASE,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &hash,
+        ),
+        Error::ReservedSessionId,
+    );
+    start(&ts, 972);
+    assert_error(
+        &ts.client.try_rekey_game(&972u32, &u32::MAX, &hash),
+        Error::ReservedSessionId,
+    );
+
+    // The queue's next id stays free for the match.
+    let target = BytesN::from_array(&ts.env, &[0x5au8; 32]);
+    ts.client.preregister_target(&QUEUE_SESSION_BASE, &target);
+    ts.client.join_queue(&ts.player1, &POINTS);
+    assert_eq!(
+        ts.client.join_queue(&ts.player2, &POINTS),
+        Some(QUEUE_SESSION_BASE)
+    );
+}
+
+#[test]
+fn test_join_queue_requires_preregistered_target() {
+    let ts = setup();
+    ts.client.join_queue(&ts.player1, &POINTS);
+    assert_error(
+        &ts.client.try_join_queue(&ts.player2, &POINTS),
+        Error::TargetNotRegistered,
+    );
+    // The failed match leaves the waiting player queued.
+    assert_eq!(ts.client.queue_length(), 1);
+    assert_error(
+        &ts.client.try_join_queue(&ts.player1, &POINTS),
+        Error::AlreadyQueued,
+    );
+}
+
+#[test]
+fn test_join_queue_applies_start_checks_on_entry() {
+    let ts = setup();
+    ts.client.set_max_points(&POINTS);
+    assert_error(
+        &ts.client.try_join_queue(&ts.player1, &(POINTS * 2)),
+        Error::PointsAboveCap,
+    );
+    ts.client.set_eligibility_required(&true);
+    assert_error(
+        &ts.client.try_join_queue(&ts.player1, &POINTS),
+        Error::NotEligible,
+    );
+    ts.client.set_eligibility_required(&false);
+    ts.client.pause();
+    assert_error(
+        &ts.client.try_join_queue(&ts.player1, &POINTS),
+        Error::ContractPaused,
+    );
+    assert_eq!(ts.client.queue_length(), 0);
+}
+
+#[test]
+fn test_join_queue_evicts_entry_that_can_no_longer_start() {
+    let ts = setup();
+    let griefer = Address::generate(&ts.env);
+    ts.client.join_queue(&griefer, &(POINTS * 2));
+    ts.client.set_max_points(&POINTS);
+    ts.client
+        .preregister_target(&QUEUE_SESSION_BASE, &test_treasure_hash(&ts.env));
+
+    assert_eq!(ts.client.join_queue(&ts.player1, &POINTS), None);
+    assert_eq!(ts.client.queue_length(), 1);
+    assert_eq!(
+        ts.client.join_queue(&ts.player2, &POINTS),
+        Some(QUEUE_SESSION_BASE)
+    );
+    assert_eq!(ts.client.queue_length(), 0);
+    assert_eq!(ts.client.get_game(&QUEUE_SESSION_BASE).player1, ts.player1);
+}
+
+#[test]
+fn test_leave_queue() {
+    let ts = setup();
+    ts.client.join_queue(&ts.player1, &POINTS);
+    ts.client.leave_queue(&ts.player1);
+    assert_eq!(ts.client.queue_length(), 0);
+    assert_error(&ts.client.try_leave_queue(&ts.player1), Error::NotQueued);
+}
+
+#[test]
+fn test_join_queue_equal_points_mismatch_waits() {
+    let ts = setup();
+    ts.client.set_require_equal_points(&true);
+    ts.client.join_queue(&ts.player1, &POINTS);
+    assert_eq!(ts.client.join_queue(&ts.player2, &(POINTS / 2)), None);
+    assert_eq!(ts.client.queue_length(), 2);
+}
+
+// ============================================================================
+// Expiry Events
+// ============================================================================
+
+/// Number of events the contract published under `topics` in the last call.
+fn count_events(ts: &TestSetup, topics: Vec<Val>) -> usize {
+    let Ok(ScVal::Vec(Some(expected))) = ScVal::try_from_val(&ts.env, &topics.to_val()) else {
+        panic!("topics convert to an XDR vector");
+    };
+    ts.env
+        .events()
+        .all()
+        .filter_by_contract(&ts.client.address)
+        .events()
+        .iter()
+        .filter(|event| {
+            let ContractEventBody::V0(body) = &event.body;
+            body.topics == expected.0
+        })
+        .count()
+}
+
+#[test]
+fn test_start_with_time_limit_publishes_deadline() {
+    let ts = setup();
+    start_with(
+        &ts,
+        670,
+        &GameOptions {
+            time_limit_seconds: Some(60),
+            ..Default::default()
+        },
+    );
+    let deadline = ts.client.effective_deadline(&670u32);
+    assert_eq!(deadline, 100 + 60 / 5 + 1);
+    // `effective_deadline` is a fresh call, so start again to inspect events.
+    start_with(
+        &ts,
+        671,
+        &GameOptions {
+            time_limit_seconds: Some(60),
+            ..Default::default()
+        },
+    );
+    let topics = (symbol_short!("game"), symbol_short!("deadline"), 671u32).into_val(&ts.env);
+    assert_eq!(count_events(&ts, topics), 1);
+
+    start(&ts, 672);
+    let topics = (symbol_short!("game"), symbol_short!("deadline"), 672u32).into_val(&ts.env);
+    assert_eq!(count_events(&ts, topics), 0);
+}
+
+#[test]
+fn test_resolve_after_time_limit_publishes_single_expiry() {
+    let ts = setup();
+    let hash = start_with(
+        &ts,
+        673,
+        &GameOptions {
+            time_limit_seconds: Some(60),
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&673u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.env.ledger().with_mut(|li| {
+        li.timestamp += 61;
+        li.sequence_number += 13;
+    });
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&673u32, &ts.player2, &valid_proof(&ts.env), &pi, &5u32),
+        Error::TimeLimitExceeded,
+    );
+
+    let topics: Vec<Val> =
+        (symbol_short!("game"), symbol_short!("expired"), 673u32).into_val(&ts.env);
+    ts.client.resolve_game(&673u32);
+    assert_eq!(count_events(&ts, topics.clone()), 1);
+    assert!(ts.client.get_game(&673u32).expiry_announced);
+
+    ts.client.resolve_game(&673u32);
+    assert_eq!(count_events(&ts, topics), 0);
+}
+
+// ============================================================================
+// Arbitration
+// ============================================================================
+
+/// Start a game with `arbiter` in which player1 has submitted; returns the
+/// arbiter.
+fn start_arbitrated(ts: &TestSetup, session_id: u32) -> Address {
+    let arbiter = Address::generate(&ts.env);
+    let hash = start_with(
+        ts,
+        session_id,
+        &GameOptions {
+            arbiter: Some(arbiter.clone()),
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&session_id, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    arbiter
+}
+
+#[test]
+fn test_arbiter_overrides_escalated_outcome() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    ts.client.set_claim_required(&true);
+    let arbiter = start_arbitrated(&ts, 680);
+    assert_eq!(ts.client.resolve_game(&680u32), Outcome::Player1Won);
+
+    ts.client.escalate(&680u32, &ts.player2);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (symbol_short!("escalated"), 680u32, arbiter.clone()).into_val(&ts.env),
+                soroban_sdk::map![&ts.env, (symbol_short!("player"), ts.player2.clone())]
+                    .into_val(&ts.env),
+            ),
+        ]
+    );
+    assert!(ts.client.get_game(&680u32).under_arbitration);
+    assert_error(
+        &ts.client.try_claim_winnings(&680u32),
+        Error::UnderArbitration,
+    );
+    assert_error(
+        &ts.client.try_escalate(&680u32, &ts.player1),
+        Error::UnderArbitration,
+    );
+
+    ts.client.arbitrate(&680u32, &Outcome::Player2Won);
+    assert_eq!(ts.env.auths()[0].0, arbiter);
+    assert_eq!(ts.client.get_outcome(&680u32), Outcome::Player2Won);
+    assert_eq!(ts.client.get_winner(&680u32), Some(ts.player2.clone()));
+
+    ts.client.claim_winnings(&680u32);
+    assert_eq!(ts.env.auths()[0].0, ts.player2);
+    assert_eq!(hub.ended(), vec![&ts.env, (680u32, 1u32)]);
+    assert_error(
+        &ts.client.try_escalate(&680u32, &ts.player1),
+        Error::EscalationClosed,
+    );
+}
+
+#[test]
+fn test_escalate_requires_arbiter_and_open_window() {
+    let ts = setup();
+    start_resolvable(&ts, 681);
+    ts.client.resolve_game(&681u32);
+    assert_error(
+        &ts.client.try_escalate(&681u32, &ts.player2),
+        Error::NoArbiter,
+    );
+
+    ts.client.set_escalation_window(&10);
+    start_arbitrated(&ts, 682);
+    assert_error(
+        &ts.client.try_escalate(&682u32, &ts.player2),
+        Error::GameNotResolved,
+    );
+    ts.client.resolve_game(&682u32);
+    let outsider = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_escalate(&682u32, &outsider),
+        Error::NotPlayer,
+    );
+    assert_error(
+        &ts.client.try_arbitrate(&682u32, &Outcome::Player2Won),
+        Error::NotUnderArbitration,
+    );
+    ts.env.ledger().with_mut(|li| li.sequence_number += 10);
+    assert_error(
+        &ts.client.try_escalate(&682u32, &ts.player2),
+        Error::EscalationClosed,
+    );
+}
+
+// ============================================================================
+// Lifecycle Events
+// ============================================================================
+
+#[test]
+fn test_lifecycle_events_follow_full_flow() {
+    let ts = setup();
+    let hash = start(&ts, 690);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (symbol_short!("game"), symbol_short!("started"), 690u32).into_val(&ts.env),
+                (ts.player1.clone(), ts.player2.clone(), hash.clone()).into_val(&ts.env),
+            ),
+        ]
+    );
+
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&690u32, &ts.player2, &valid_proof(&ts.env), &pi, &20u32);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (symbol_short!("submit"), 690u32).into_val(&ts.env),
+                20u32.into_val(&ts.env),
+            ),
+            (
+                ts.client.address.clone(),
+                (
+                    symbol_short!("game"),
+                    Symbol::new(&ts.env, "submitted"),
+                    690u32
+                )
+                    .into_val(&ts.env),
+                (ts.player2.clone(), false).into_val(&ts.env),
+            ),
+        ]
+    );
+
+    ts.client.resolve_game(&690u32);
+    let resolved: Vec<Val> =
+        (symbol_short!("game"), symbol_short!("resolved"), 690u32).into_val(&ts.env);
+    assert_eq!(count_events(&ts, resolved.clone()), 1);
+    let events = ts.env.events().all().filter_by_contract(&ts.client.address);
+    let ContractEventBody::V0(body) = &events.events()[0].body;
+    assert_eq!(
+        body.data,
+        ScVal::try_from_val(
+            &ts.env,
+            &IntoVal::<Env, Val>::into_val(&Outcome::Player2Won, &ts.env)
+        )
+        .unwrap()
+    );
+
+    // The idempotent path does not announce the resolution again.
+    ts.client.resolve_game(&690u32);
+    assert_eq!(count_events(&ts, resolved), 0);
+}
+
+// ============================================================================
+// Verifier Input Budget
+// ============================================================================
+
+#[test]
+fn test_verifier_budget_accepts_inputs_at_budget() {
+    let ts = setup();
+    assert_eq!(ts.client.get_max_verifier_input_budget(), None);
+    // 64 proof bytes plus 32 public-input bytes.
+    ts.client.set_max_verifier_input_budget(&96u32);
+    let hash = start(&ts, 700);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&700u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(ts.client.get_game(&700u32).player1_energy, Some(10));
+}
+
+#[test]
+fn test_verifier_budget_rejects_inputs_beyond_budget() {
+    let ts = setup();
+    ts.client.set_max_verifier_input_budget(&95u32);
+    let hash = start(&ts, 701);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&701u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32),
+        Error::VerifierBudgetExceeded,
+    );
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&701u32, &ts.player1, &valid_proof(&ts.env), &pi),
+        vec![&ts.env, Error::VerifierBudgetExceeded]
+    );
+    assert_eq!(ts.client.get_game(&701u32).submissions, 0);
+}
+
+#[test]
+fn test_check_public_inputs() {
+    let ts = setup();
+    let short = Bytes::from_slice(&ts.env, &[0xAB; 16]);
+    assert_error(
+        &ts.client.try_check_public_inputs(&952u32, &short),
+        Error::GameNotFound,
+    );
+    let hash = start(&ts, 952);
+    let before = ts.client.get_game(&952u32);
+
+    ts.client
+        .check_public_inputs(&952u32, &treasure_hash_as_bytes(&ts.env, &hash));
+    let wrong = Bytes::from_slice(&ts.env, &[0xCD; 32]);
+    for inputs in [wrong, short] {
+        assert_error(
+            &ts.client.try_check_public_inputs(&952u32, &inputs),
+            Error::PublicInputMismatch,
+        );
+    }
+    assert_eq!(ts.client.get_game(&952u32), before);
+}
+
+#[test]
+fn test_oversized_proof_rejected_before_verifier() {
+    let ts = setup();
+    assert_eq!(ts.client.get_max_proof_len(), 32 * 1024);
+    ts.client.set_max_proof_len(&63u32);
+    let hash = start(&ts, 945);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    // The invalid proof would trap the verifier; the length check stops it first.
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&945u32, &ts.player1, &invalid_proof(&ts.env), &pi, &10u32),
+        Error::ProofTooLarge,
+    );
+    assert_eq!(
+        ts.client
+            .submission_diagnostics(&945u32, &ts.player1, &valid_proof(&ts.env), &pi),
+        vec![&ts.env, Error::ProofTooLarge]
+    );
+
+    ts.client.set_max_proof_len(&64u32);
+    ts.client
+        .submit_zk_proof(&945u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert_eq!(ts.client.get_game(&945u32).player1_energy, Some(10));
+}
+
+#[test]
+fn test_short_public_inputs_rejected() {
+    let ts = setup();
+    ts.client.set_debug_mode(&true);
+    start(&ts, 946);
+    let short = Bytes::from_slice(&ts.env, &[0xAB; 16]);
+    // Rejected outright, even in debug mode.
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&946u32, &ts.player1, &valid_proof(&ts.env), &short, &10u32),
+        Error::PublicInputMismatch,
+    );
+    assert_eq!(
+        ts.client.get_last_rejected_inputs(&946u32, &ts.player1),
+        None
+    );
+}
+
+// ============================================================================
+// Deadline Ledger
+// ============================================================================
+
+/// Start a game whose proofs are accepted up to ledger 150.
+fn start_with_deadline(ts: &TestSetup, session_id: u32) -> Bytes {
+    let hash = start_with(
+        ts,
+        session_id,
+        &GameOptions {
+            deadline_ledger: Some(150),
+            ..Default::default()
+        },
+    );
+    treasure_hash_as_bytes(&ts.env, &hash)
+}
+
+#[test]
+fn test_cannot_win_after_deadline_without_proof() {
+    let ts = setup();
+    let pi = start_with_deadline(&ts, 955);
+    ts.client
+        .submit_zk_proof(&955u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 150);
+    assert!(ts.client.can_still_win(&955u32, &ts.player2));
+
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    assert!(!ts.client.can_still_win(&955u32, &ts.player2));
+    assert!(ts.client.can_still_win(&955u32, &ts.player1));
+}
+
+#[test]
+fn test_claim_timeout_rejected_before_deadline() {
+    let ts = setup();
+    start_with_deadline(&ts, 710);
+    assert_eq!(ts.client.effective_deadline(&710u32), 151);
+    assert_error(
+        &ts.client.try_claim_timeout(&710u32),
+        Error::DeadlineNotReached,
+    );
+    ts.env.ledger().with_mut(|li| li.sequence_number = 150);
+    assert_error(
+        &ts.client.try_claim_timeout(&710u32),
+        Error::DeadlineNotReached,
+    );
+
+    start(&ts, 711);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 10_000);
+    assert_error(
+        &ts.client.try_claim_timeout(&711u32),
+        Error::DeadlineNotReached,
+    );
+}
+
+#[test]
+fn test_claim_timeout_forfeits_to_submitter() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    let pi = start_with_deadline(&ts, 712);
+    ts.client
+        .submit_zk_proof(&712u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&712u32, &ts.player2, &valid_proof(&ts.env), &pi, &5u32),
+        Error::DeadlinePassed,
+    );
+    assert_eq!(ts.client.claim_timeout(&712u32), Outcome::Player1Won);
+    assert_eq!(hub.ended(), vec![&ts.env, (712u32, 0u32)]);
+    assert_error(
+        &ts.client.try_claim_timeout(&712u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_claim_timeout_silent_game_resolves_neither() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_with_deadline(&ts, 713);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    assert_eq!(ts.client.claim_timeout(&713u32), Outcome::NeitherFound);
+    assert_eq!(hub.ended(), vec![&ts.env, (713u32, 3u32)]);
+}
+
+#[test]
+fn test_both_submitted_before_deadline_resolves_normally() {
+    let ts = setup();
+    let pi = start_with_deadline(&ts, 714);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 150);
+    ts.client
+        .submit_zk_proof(&714u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&714u32, &ts.player2, &valid_proof(&ts.env), &pi, &5u32);
+    assert_eq!(ts.client.resolve_game(&714u32), Outcome::Player2Won);
+
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    assert_error(
+        &ts.client.try_claim_timeout(&714u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_start_rejects_past_deadline() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    assert_error(
+        &ts.client.try_start_game_with_options(
+            &715u32,
+            &ts.player1,
+            &ts.player2,
+            &POINTS,
+            &POINTS,
+            &hash,
+            &GameOptions {
+                deadline_ledger: Some(99),
+                ..Default::default()
+            },
+        ),
+        Error::DeadlinePassed,
+    );
+}
+
+// ============================================================================
+// Paused Verifier Refund
+// ============================================================================
+
+#[test]
+fn test_paused_verifier_refunds_game() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    let verifier = ts.env.register(MockPausableVerifier, ());
+    let verifier_client = MockPausableVerifierClient::new(&ts.env, &verifier);
+    ts.client.set_verifier(&verifier);
+    ts.client.set_paused_verifier_refund(&true);
+    ts.client.set_paused_refund_delay(&600);
+    start_resolvable(&ts, 730);
+
+    verifier_client.set_paused(&true);
+    assert_error(
+        &ts.client.try_resolve_if_verifier_paused(&730u32),
+        Error::PausedRefundTooEarly,
+    );
+    ts.env.ledger().with_mut(|li| li.timestamp += 600);
+    assert_eq!(
+        ts.client.resolve_if_verifier_paused(&730u32),
+        Outcome::NeitherFound
+    );
+    assert_eq!(hub.refunded(), vec![&ts.env, 730u32]);
+    assert_eq!(ts.client.get_outcome(&730u32), Outcome::NeitherFound);
+    assert_error(
+        &ts.client.try_resolve_if_verifier_paused(&730u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_paused_verifier_refund_requires_pause_and_flag() {
+    let ts = setup();
+    let verifier = ts.env.register(MockPausableVerifier, ());
+    ts.client.set_verifier(&verifier);
+    ts.client.set_paused_refund_delay(&0);
+    start(&ts, 731);
+    assert_error(
+        &ts.client.try_resolve_if_verifier_paused(&731u32),
+        Error::PausedRefundDisabled,
+    );
+
+    ts.client.set_paused_verifier_refund(&true);
+    assert_error(
+        &ts.client.try_resolve_if_verifier_paused(&731u32),
+        Error::VerifierNotPaused,
+    );
+
+    // A verifier without `is_paused` never counts as paused.
+    ts.client.set_verifier(&ts.verifier_addr);
+    assert_error(
+        &ts.client.try_resolve_if_verifier_paused(&731u32),
+        Error::VerifierNotPaused,
+    );
+    assert!(!ts.client.get_game(&731u32).resolved);
+}
+
+// ============================================================================
+// Pause Switch
+// ============================================================================
+
+#[test]
+fn test_pause_blocks_starts_and_submissions() {
+    let ts = setup();
+    let hash = start(&ts, 740);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client.pause();
+    assert!(ts.client.is_paused());
+
+    assert_error(
+        &ts.client
+            .try_start_game(&741u32, &ts.player1, &ts.player2, &POINTS, &POINTS, &hash),
+        Error::ContractPaused,
+    );
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&740u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32),
+        Error::ContractPaused,
+    );
+
+    ts.client.unpause();
+    ts.client
+        .submit_zk_proof(&740u32, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    start(&ts, 741);
+}
+
+#[test]
+fn test_resolve_succeeds_while_paused() {
+    let ts = setup();
+    start_resolvable(&ts, 742);
+    ts.client.pause();
+    assert_eq!(ts.client.resolve_game(&742u32), Outcome::Player1Won);
+    assert_eq!(ts.client.get_outcome(&742u32), Outcome::Player1Won);
+}
+
+#[test]
+fn test_only_admin_toggles_pause() {
+    let ts = setup();
+    let outsider = Address::generate(&ts.env);
+    mock_single_auth(&ts, &outsider, "pause", Vec::new(&ts.env));
+    assert!(ts.client.try_pause().is_err());
+    assert!(!ts.client.is_paused());
+
+    let admin = ts.client.get_admin();
+    mock_single_auth(&ts, &admin, "pause", Vec::new(&ts.env));
+    ts.client.pause();
+    mock_single_auth(&ts, &outsider, "unpause", Vec::new(&ts.env));
+    assert!(ts.client.try_unpause().is_err());
+    assert!(ts.client.is_paused());
+}
+
+// ============================================================================
+// Earliest Verified Mode
+// ============================================================================
+
+/// Player `first` verifies with 50 energy at ledger 100, then `second` with
+/// 10 energy at ledger 105; returns the resolved outcome.
+fn resolve_staggered(
+    ts: &TestSetup,
+    session_id: u32,
+    mode: GameMode,
+    player1_first: bool,
+) -> Outcome {
+    let hash = start_with(
+        ts,
+        session_id,
+        &GameOptions {
+            mode,
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let (first, second) = if player1_first {
+        (&ts.player1, &ts.player2)
+    } else {
+        (&ts.player2, &ts.player1)
+    };
+    ts.client
+        .submit_zk_proof(&session_id, first, &valid_proof(&ts.env), &pi, &50u32);
+    ts.env.ledger().with_mut(|li| {
+        li.sequence_number += 5;
+        li.timestamp += 25;
+    });
+    ts.client
+        .submit_zk_proof(&session_id, second, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&session_id)
+}
+
+#[test]
+fn test_earliest_verified_awards_earlier_submitter() {
+    let ts = setup();
+    assert_eq!(
+        resolve_staggered(&ts, 750, GameMode::EarliestVerified, true),
+        Outcome::Player1Won
+    );
+    let game = ts.client.get_game(&750u32);
+    assert_eq!(game.player1_verified_ledger, Some(100));
+    assert_eq!(game.player1_verified_at, Some(1_700_000_000));
+    assert_eq!(game.player2_verified_ledger, Some(105));
+    assert_eq!(game.player2_verified_at, Some(1_700_000_025));
+
+    assert_eq!(
+        resolve_staggered(&ts, 751, GameMode::EarliestVerified, false),
+        Outcome::Player2Won
+    );
+}
+
+#[test]
+fn test_lowest_energy_mode_ignores_submission_order() {
+    let ts = setup();
+    assert_eq!(
+        resolve_staggered(&ts, 752, GameMode::Inherit, true),
+        Outcome::Player2Won
+    );
+}
+
+// ============================================================================
+// Result Attestations
+// ============================================================================
+
+/// Sign `session_id` (little-endian) followed by `outcome_code`, the
+/// message `attest_result` verifies.
+fn sign_result(ts: &TestSetup, key: &SigningKey, session_id: u32, outcome_code: u8) -> BytesN<64> {
+    let mut message = session_id.to_le_bytes().to_vec();
+    message.push(outcome_code);
+    BytesN::from_array(&ts.env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_attest_and_retrieve_result() {
+    let ts = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    ts.client.set_attestation_key(&BytesN::from_array(
+        &ts.env,
+        &key.verifying_key().to_bytes(),
+    ));
+    start_resolvable(&ts, 760);
+    ts.client.resolve_game(&760u32);
+    assert_error(
+        &ts.client.try_get_result_attestation(&760u32),
+        Error::NoAttestation,
+    );
+
+    let signature = sign_result(&ts, &key, 760, 0);
+    ts.client.attest_result(&760u32, &signature);
+    assert_eq!(
+        ts.client.get_result_attestation(&760u32),
+        (Outcome::Player1Won, signature)
+    );
+}
+
+#[test]
+fn test_attest_rejects_wrong_outcome_and_live_games() {
+    let ts = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    start_resolvable(&ts, 761);
+    let signature = sign_result(&ts, &key, 761, 0);
+    assert_error(
+        &ts.client.try_attest_result(&761u32, &signature),
+        Error::GameNotResolved,
+    );
+    ts.client.resolve_game(&761u32);
+    assert_error(
+        &ts.client.try_attest_result(&761u32, &signature),
+        Error::NoAttestationKey,
+    );
+
+    ts.client.set_attestation_key(&BytesN::from_array(
+        &ts.env,
+        &key.verifying_key().to_bytes(),
+    ));
+    // A signature over another outcome does not verify.
+    let wrong = sign_result(&ts, &key, 761, 1);
+    assert!(ts.client.try_attest_result(&761u32, &wrong).is_err());
+    assert_error(
+        &ts.client.try_get_result_attestation(&761u32),
+        Error::NoAttestation,
+    );
+}
+
+// ============================================================================
+// Second-Player Window
+// ============================================================================
+
+#[test]
+fn test_second_player_window_blocks_resolution_until_closed() {
+    let ts = setup();
+    ts.client.set_second_player_window(&20);
+    start_resolvable(&ts, 770);
+    assert_eq!(ts.client.get_game(&770u32).first_verified_ledger, Some(100));
+
+    assert_error(
+        &ts.client.try_resolve_game(&770u32),
+        Error::SecondPlayerWindowOpen,
+    );
+    ts.env.ledger().with_mut(|li| li.sequence_number += 19);
+    assert_error(
+        &ts.client.try_resolve_game_as(&770u32, &ts.player1),
+        Error::SecondPlayerWindowOpen,
+    );
+
+    ts.env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert_eq!(ts.client.resolve_game(&770u32), Outcome::Player1Won);
+}
+
+#[test]
+fn test_second_player_window_closes_once_both_verify() {
+    let ts = setup();
+    ts.client.set_second_player_window(&20);
+    let hash = start(&ts, 771);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    ts.client
+        .submit_zk_proof(&771u32, &ts.player1, &proof, &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&771u32, &ts.player2, &proof, &pi, &20u32);
+
+    assert_eq!(ts.client.resolve_game(&771u32), Outcome::Player1Won);
+}
+
+// ============================================================================
+// Cancellation
+// ============================================================================
+
+#[test]
+fn test_players_cancel_unverified_game() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start(&ts, 780);
+
+    let reason = Some(symbol_short!("no_show"));
+    ts.client.cancel_game(&780u32, &reason);
+    let signers: std::vec::Vec<Address> = ts.env.auths().into_iter().map(|a| a.0).collect();
+    assert_eq!(signers, std::vec![ts.player1.clone(), ts.player2.clone()]);
+    assert_eq!(
+        ts.env.events().all().filter_by_contract(&ts.client.address),
+        vec![
+            &ts.env,
+            (
+                ts.client.address.clone(),
+                (symbol_short!("game"), symbol_short!("cancelled"), 780u32).into_val(&ts.env),
+                soroban_sdk::map![
+                    &ts.env,
+                    (
+                        Symbol::new(&ts.env, "hub_session_id"),
+                        IntoVal::<Env, Val>::into_val(&780u32, &ts.env)
+                    ),
+                    (
+                        symbol_short!("reason"),
+                        IntoVal::<Env, Val>::into_val(&reason, &ts.env)
+                    )
+                ]
+                .into_val(&ts.env),
+            ),
+        ]
+    );
+    assert_eq!(ts.client.get_cancel_reason(&780u32), reason);
+    assert_eq!(hub.cancelled(), vec![&ts.env, 780u32]);
+    assert_eq!(hub.refunded().len(), 0);
+    assert_eq!(ts.client.phase_counts(), counts(0, 0, 0, 0));
+    assert_eq!(ts.client.active_session_count(), 0);
+    assert_eq!(
+        ts.client.active_game_between(&ts.player1, &ts.player2),
+        None
+    );
+
+    // The storage entry is gone, so the session id can be reused.
+    assert_error(&ts.client.try_get_game(&780u32), Error::GameNotFound);
+    start(&ts, 780);
+}
+
+#[test]
+fn test_cancel_rejected_after_a_submission() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 781);
+
+    assert_error(
+        &ts.client.try_cancel_game(&781u32, &None),
+        Error::AlreadySubmitted,
+    );
+    assert!(hub.cancelled().is_empty());
+    ts.client.resolve_game(&781u32);
+    assert_error(
+        &ts.client.try_cancel_game(&781u32, &None),
+        Error::GameAlreadyResolved,
+    );
+}
+
+// ============================================================================
+// Per-Session Verifier Override
+// ============================================================================
+
+#[test]
+fn test_pinned_verifier_only_affects_its_session() {
+    let ts = setup();
+    let rejecting = ts.env.register(MockRejectingVerifier, ());
+    let hash = start_with(
+        &ts,
+        790,
+        &GameOptions {
+            verifier_override: Some(rejecting.clone()),
+            ..Default::default()
+        },
+    );
+    start(&ts, 791);
+    assert_eq!(ts.client.get_session_verifier(&790u32), rejecting);
+    assert_eq!(ts.client.get_session_verifier(&791u32), ts.verifier_addr);
+
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    assert!(ts
+        .client
+        .try_submit_zk_proof(&790u32, &ts.player1, &proof, &pi, &10u32)
+        .is_err());
+    ts.client
+        .submit_zk_proof(&791u32, &ts.player1, &proof, &pi, &10u32);
+    assert_eq!(ts.client.resolve_game(&791u32), Outcome::Player1Won);
+    assert_error(
+        &ts.client.try_resolve_game(&790u32),
+        Error::NeitherPlayerSubmitted,
+    );
+}
+
+// ============================================================================
+// Resolution Diagnostics
+// ============================================================================
+
+#[test]
+fn test_resolve_diagnostics_reports_blocking_error() {
+    let ts = setup();
+    let keeper = Address::generate(&ts.env);
+    assert_error(
+        &ts.client.try_resolve_diagnostics(&800u32, &keeper),
+        Error::GameNotFound,
+    );
+
+    start(&ts, 800);
+    assert_error(
+        &ts.client.try_resolve_diagnostics(&800u32, &keeper),
+        Error::NeitherPlayerSubmitted,
+    );
+
+    start_resolvable(&ts, 801);
+    ts.client.set_players_only_resolve(&true);
+    assert_error(
+        &ts.client.try_resolve_diagnostics(&801u32, &keeper),
+        Error::ResolutionGated,
+    );
+    assert_eq!(
+        ts.client.resolve_diagnostics(&801u32, &ts.player2),
+        Outcome::Player1Won
+    );
+
+    ts.client.set_players_only_resolve(&false);
+    ts.client.set_second_player_window(&10);
+    start_resolvable(&ts, 802);
+    assert_error(
+        &ts.client.try_resolve_diagnostics(&802u32, &keeper),
+        Error::SecondPlayerWindowOpen,
+    );
+}
+
+#[test]
+fn test_resolve_diagnostics_does_not_resolve() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    let keeper = Address::generate(&ts.env);
+    start_resolvable(&ts, 803);
+
+    assert_eq!(
+        ts.client.resolve_diagnostics(&803u32, &keeper),
+        Outcome::Player1Won
+    );
+    assert!(!ts.client.get_game(&803u32).resolved);
+    assert!(hub.ended().is_empty());
+
+    ts.client.resolve_game(&803u32);
+    assert_eq!(
+        ts.client.resolve_diagnostics(&803u32, &keeper),
+        Outcome::Player1Won
+    );
+}
+
+// ============================================================================
+// Verifier Stats
+// ============================================================================
+
+#[test]
+fn test_verifier_stats_aggregate_proof_lengths() {
+    let ts = setup();
+    assert_eq!(
+        ts.client.verifier_stats(&ts.verifier_addr),
+        VerifierStats::default()
+    );
+
+    for (session_id, len) in [(810u32, 64u32), (811, 16), (812, 200)] {
+        let hash = start(&ts, session_id);
+        let pi = treasure_hash_as_bytes(&ts.env, &hash);
+        let proof = Bytes::from_slice(&ts.env, &[1u8; 200][..len as usize]);
+        ts.client
+            .submit_zk_proof(&session_id, &ts.player1, &proof, &pi, &10u32);
+    }
+    // A rejected proof is not counted.
+    let hash = start(&ts, 813);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let _ =
+        ts.client
+            .try_submit_zk_proof(&813u32, &ts.player1, &invalid_proof(&ts.env), &pi, &10u32);
+
+    assert_eq!(
+        ts.client.verifier_stats(&ts.verifier_addr),
+        VerifierStats {
+            count: 3,
+            min_len: 16,
+            max_len: 200,
+            sum_len: 280,
+        }
+    );
+}
+
+// ============================================================================
+// Player Stats
+// ============================================================================
+
+fn stats(wins: u32, losses: u32, draws: u32) -> PlayerStats {
+    PlayerStats {
+        wins,
+        losses,
+        draws,
+    }
+}
+
+#[test]
+fn test_player_stats_tally_resolved_games() {
+    let ts = setup();
+    assert_eq!(ts.client.get_stats(&ts.player1), PlayerStats::default());
+
+    // Player 1 wins solo, and a repeat resolve does not count again.
+    start_resolvable(&ts, 820);
+    ts.client.resolve_game(&820u32);
+    ts.client.resolve_game(&820u32);
+
+    // Player 2 wins on energy.
+    let hash = start(&ts, 821);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    ts.client
+        .submit_zk_proof(&821u32, &ts.player1, &proof, &pi, &50u32);
+    ts.client
+        .submit_zk_proof(&821u32, &ts.player2, &proof, &pi, &20u32);
+    ts.client.resolve_game(&821u32);
+
+    // Equal energy is a draw for both.
+    let hash = start(&ts, 822);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&822u32, &ts.player1, &proof, &pi, &30u32);
+    ts.client
+        .submit_zk_proof(&822u32, &ts.player2, &proof, &pi, &30u32);
+    ts.client.resolve_game(&822u32);
+
+    // A timed-out game nobody proved is a loss for both.
+    start_with_deadline(&ts, 823);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    ts.client.claim_timeout(&823u32);
+
+    assert_eq!(ts.client.get_stats(&ts.player1), stats(1, 2, 1));
+    assert_eq!(ts.client.get_stats(&ts.player2), stats(1, 2, 1));
+}
+
+// ============================================================================
+// Hub Outcome Reporting
+// ============================================================================
+
+#[test]
+fn test_hub_receives_explicit_outcome_codes() {
+    let ts = setup();
+    let hub = MockGameHubClient::new(&ts.env, &ts.client.get_hub());
+    assert_eq!(hub.last_outcome(), None);
+
+    // A tie is reported as a draw, even under the default
+    // `TieDefault::Player1`.
+    assert_eq!(ts.client.get_tie_default(), TieDefault::Player1);
+    let hash = start(&ts, 830);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    let proof = valid_proof(&ts.env);
+    for player in [&ts.player1, &ts.player2] {
+        ts.client
+            .submit_zk_proof(&830u32, player, &proof, &pi, &40u32);
+    }
+    assert_eq!(ts.client.resolve_game(&830u32), Outcome::BothFoundTreasure);
+    assert_eq!(hub.last_outcome(), Some(2));
+
+    let hash = start(&ts, 831);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&831u32, &ts.player2, &proof, &pi, &40u32);
+    ts.client.resolve_game(&831u32);
+    assert_eq!(hub.last_outcome(), Some(1));
+
+    start_with_deadline(&ts, 832);
+    ts.env.ledger().with_mut(|li| li.sequence_number = 151);
+    assert_eq!(ts.client.claim_timeout(&832u32), Outcome::NeitherFound);
+    assert_eq!(hub.last_outcome(), Some(3));
+}
+
+// ============================================================================
+// Currency Metadata
+// ============================================================================
+
+fn currency_options(ts: &TestSetup, decimals: u32, symbol: &[u8]) -> GameOptions {
+    GameOptions {
+        currency_decimals: Some(decimals),
+        currency_symbol: Some(Bytes::from_slice(&ts.env, symbol)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_currency_metadata_stored_per_game() {
+    let ts = setup();
+    start_with(&ts, 840, &currency_options(&ts, 7, b"XLM"));
+    start_with(&ts, 841, &currency_options(&ts, 18, b"WETH"));
+    start(&ts, 842);
+
+    let xlm = ts.client.get_game(&840u32);
+    assert_eq!(xlm.currency_decimals, Some(7));
+    assert_eq!(
+        xlm.currency_symbol,
+        Some(Bytes::from_slice(&ts.env, b"XLM"))
+    );
+    let weth = ts.client.get_game(&841u32);
+    assert_eq!(weth.currency_decimals, Some(18));
+    assert_eq!(
+        weth.currency_symbol,
+        Some(Bytes::from_slice(&ts.env, b"WETH"))
+    );
+    let plain = ts.client.get_game(&842u32);
+    assert_eq!(plain.currency_decimals, None);
+    assert_eq!(plain.currency_symbol, None);
+}
+
+#[test]
+fn test_currency_metadata_bounds() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    for options in [
+        currency_options(&ts, MAX_CURRENCY_DECIMALS + 1, b"XLM"),
+        currency_options(&ts, 7, b""),
+        currency_options(&ts, 7, b"THIRTEENBYTES"),
+    ] {
+        assert_error(
+            &ts.client.try_start_game_with_options(
+                &843u32,
+                &ts.player1,
+                &ts.player2,
+                &POINTS,
+                &POINTS,
+                &hash,
+                &options,
+            ),
+            Error::InvalidCurrency,
+        );
+    }
+    start_with(
+        &ts,
+        843,
+        &currency_options(&ts, MAX_CURRENCY_DECIMALS, b"TWELVEBYTES!"),
+    );
+}
+
+// ============================================================================
+// Rematch
+// ============================================================================
+
+#[test]
+fn test_resolve_and_rematch_starts_new_game() {
+    let ts = setup();
+    start_resolvable(&ts, 850);
+    let target = BytesN::from_array(&ts.env, &[0x51u8; 32]);
+    ts.client.preregister_target(&851u32, &target);
+
+    assert_eq!(
+        ts.client.resolve_and_rematch(&850u32, &851u32),
+        (Outcome::Player1Won, true)
+    );
+    let signers: std::vec::Vec<Address> = ts.env.auths().into_iter().map(|a| a.0).collect();
+    assert_eq!(signers, std::vec![ts.player1.clone(), ts.player2.clone()]);
+
+    assert!(ts.client.get_game(&850u32).resolved);
+    let rematch = ts.client.get_game(&851u32);
+    assert!(!rematch.resolved);
+    assert_eq!(rematch.treasure_hash, target);
+    assert_eq!(
+        (rematch.player1, rematch.player2),
+        (ts.player1.clone(), ts.player2.clone())
+    );
+    assert_eq!(
+        (rematch.player1_points, rematch.player2_points),
+        (POINTS, POINTS)
+    );
+}
+
+#[test]
+fn test_rematch_derives_fresh_target() {
+    let ts = setup();
+    start_resolvable(&ts, 940);
+    ts.client.resolve_game(&940u32);
+
+    let target = BytesN::from_array(&ts.env, &[0x94u8; 32]);
+    ts.client
+        .rematch(&940u32, &941u32, &(POINTS * 2), &POINTS, &target);
+    let signers: std::vec::Vec<Address> = ts.env.auths().into_iter().map(|a| a.0).collect();
+    assert_eq!(signers, std::vec![ts.player1.clone(), ts.player2.clone()]);
+
+    let old = ts.client.get_game(&940u32);
+    let new = ts.client.get_game(&941u32);
+    assert!(!new.resolved);
+    assert_ne!(new.treasure_hash, old.treasure_hash);
+    assert_ne!(new.nullifier, old.nullifier);
+    assert_eq!(
+        (new.player1, new.player2),
+        (ts.player1.clone(), ts.player2.clone())
+    );
+    assert_eq!(
+        (new.player1_points, new.player2_points),
+        (POINTS * 2, POINTS)
+    );
+}
+
+#[test]
+fn test_rematch_requires_resolved_game_and_free_session() {
+    let ts = setup();
+    let target = BytesN::from_array(&ts.env, &[0x94u8; 32]);
+    assert_error(
+        &ts.client
+            .try_rematch(&942u32, &943u32, &POINTS, &POINTS, &target),
+        Error::GameNotFound,
+    );
+    start_resolvable(&ts, 942);
+    assert_error(
+        &ts.client
+            .try_rematch(&942u32, &943u32, &POINTS, &POINTS, &target),
+        Error::GameNotResolved,
+    );
+    ts.client.resolve_game(&942u32);
+    start_resolvable(&ts, 944);
+    ts.client.resolve_game(&944u32);
+    assert_error(
+        &ts.client
+            .try_rematch(&942u32, &944u32, &POINTS, &POINTS, &target),
+        Error::SessionExists,
+    );
+}
+
+#[test]
+fn test_rejected_rematch_keeps_resolution() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 852);
+    start_resolvable(&ts, 854);
+
+    // No target registered for the rematch.
+    assert_eq!(
+        ts.client.resolve_and_rematch(&852u32, &853u32),
+        (Outcome::Player1Won, false)
+    );
+    assert!(ts.client.get_game(&852u32).resolved);
+
+    // The start itself is rejected while the contract is paused.
+    ts.client
+        .preregister_target(&855u32, &BytesN::from_array(&ts.env, &[0x55u8; 32]));
+    ts.client.pause();
+    assert_eq!(
+        ts.client.resolve_and_rematch(&854u32, &855u32),
+        (Outcome::Player1Won, false)
+    );
+    assert!(ts.client.get_game(&854u32).resolved);
+    assert_error(&ts.client.try_get_game(&855u32), Error::GameNotFound);
+    assert_eq!(hub.ended(), vec![&ts.env, (852u32, 0u32), (854u32, 0u32)]);
+    assert_eq!(hub.started().len(), 2);
+}
+
+// ============================================================================
+// Outcome Preview
+// ============================================================================
+
+#[test]
+fn test_peek_outcome_tracks_standing_without_resolving() {
+    let ts = setup();
+    assert_error(&ts.client.try_peek_outcome(&860u32), Error::GameNotFound);
+    let hash = start(&ts, 860);
+    assert_eq!(ts.client.peek_outcome(&860u32), Outcome::NeitherFound);
+
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&860u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    let before = ts.client.get_game(&860u32);
+    assert_eq!(ts.client.peek_outcome(&860u32), Outcome::Player2Won);
+    assert_eq!(ts.client.get_game(&860u32), before);
+    assert!(!before.resolved);
+    assert_eq!(ts.client.phase_counts(), counts(0, 1, 0, 0));
+
+    ts.client.resolve_game(&860u32);
+    assert_eq!(ts.client.peek_outcome(&860u32), Outcome::Player2Won);
+}
+
+// ============================================================================
+// Batch Resolution
+// ============================================================================
+
+#[test]
+fn test_resolve_many_collects_per_session_results() {
+    let ts = setup();
+    start_resolvable(&ts, 870);
+    start_resolvable(&ts, 871);
+    ts.client.resolve_game(&871u32);
+    start(&ts, 872);
+
+    let results = ts
+        .client
+        .resolve_many(&vec![&ts.env, 870u32, 899u32, 871u32, 872u32]);
+    assert_eq!(
+        results,
+        vec![
+            &ts.env,
+            Ok(Outcome::Player1Won),
+            Err(Error::GameNotFound),
+            Ok(Outcome::Player1Won),
+            Err(Error::NeitherPlayerSubmitted),
+        ]
+    );
+    assert!(ts.client.get_game(&870u32).resolved);
+    assert!(!ts.client.get_game(&872u32).resolved);
+    assert_eq!(ts.client.phase_counts(), counts(1, 0, 0, 2));
+}
+
+#[test]
+fn test_resolve_many_isolates_a_trapping_hub() {
+    let ts = setup();
+    let hub = use_recording_hub(&ts);
+    start_resolvable(&ts, 956);
+    ts.client.set_hub(&ts.env.register(TrappingHub, ()));
+    start_resolvable(&ts, 957);
+
+    let results = ts.client.resolve_many(&vec![&ts.env, 957u32, 956u32]);
+    assert_eq!(
+        results,
+        vec![&ts.env, Err(Error::HubCallFailed), Ok(Outcome::Player1Won)]
+    );
+    assert!(!ts.client.get_game(&957u32).resolved);
+    assert!(ts.client.get_game(&956u32).resolved);
+    assert_eq!(hub.ended(), vec![&ts.env, (956u32, 0u32)]);
+    assert_eq!(ts.client.phase_counts(), counts(0, 1, 0, 1));
+    // Outside a batch the same failure aborts the call.
+    assert_error(&ts.client.try_resolve_game(&957u32), Error::HubCallFailed);
+}
+
+#[test]
+fn test_resolve_many_rejects_oversized_batch() {
+    let ts = setup();
+    let mut session_ids = Vec::new(&ts.env);
+    for session_id in 0..51u32 {
+        session_ids.push_back(session_id);
+    }
+    assert_error(
+        &ts.client.try_resolve_many(&session_ids),
+        Error::BatchTooLarge,
+    );
+}
+
+// ============================================================================
+// Windowed Outcome Stats
+// ============================================================================
+
+#[test]
+fn test_stats_since_counts_by_bucket() {
+    let ts = setup();
+    // Ledger 100 is in bucket 0.
+    start_resolvable(&ts, 880);
+    ts.client.resolve_game(&880u32);
+
+    ts.env
+        .ledger()
+        .with_mut(|li| li.sequence_number = OUTCOME_BUCKET_LEDGERS + 5);
+    let hash = start(&ts, 881);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&881u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+    ts.client.resolve_game(&881u32);
+    start_resolvable(&ts, 882);
+    ts.client.resolve_game(&882u32);
+    // A repeat resolve is not counted again.
+    ts.client.resolve_game(&882u32);
+
+    let all = ts.client.stats_since(&0);
+    assert_eq!(
+        all,
+        OutcomeStats {
+            player1_won: 2,
+            player2_won: 1,
+            both_found: 0,
+            neither_found: 0,
+        }
+    );
+    // Any ledger in bucket 1 covers the whole bucket.
+    let recent = OutcomeStats {
+        player1_won: 1,
+        player2_won: 1,
+        both_found: 0,
+        neither_found: 0,
+    };
+    assert_eq!(ts.client.stats_since(&OUTCOME_BUCKET_LEDGERS), recent);
+    assert_eq!(ts.client.stats_since(&(OUTCOME_BUCKET_LEDGERS + 9)), recent);
+    assert_eq!(
+        ts.client.stats_since(&(2 * OUTCOME_BUCKET_LEDGERS)),
+        OutcomeStats::default()
+    );
+
+    // Buckets older than the lookback window drop out.
+    ts.env.ledger().with_mut(|li| {
+        li.sequence_number = MAX_STATS_BUCKETS * OUTCOME_BUCKET_LEDGERS;
+    });
+    assert_eq!(ts.client.stats_since(&0), recent);
+}
+
+// ============================================================================
+// Late Submit Policy
+// ============================================================================
+
+/// Start a `FirstWins` game that player 1 has already won; returns the
+/// public inputs.
+fn start_first_won(ts: &TestSetup, session_id: u32) -> Bytes {
+    let hash = start_with(
+        ts,
+        session_id,
+        &GameOptions {
+            mode: GameMode::FirstWins,
+            ..Default::default()
+        },
+    );
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&session_id, &ts.player1, &valid_proof(&ts.env), &pi, &10u32);
+    assert!(ts.client.get_game(&session_id).resolved);
+    pi
+}
+
+#[test]
+fn test_late_submission_rejected_by_default() {
+    let ts = setup();
+    assert_eq!(ts.client.get_late_submit_policy(), LateSubmitPolicy::Reject);
+    let pi = start_first_won(&ts, 890);
+    let proof = valid_proof(&ts.env);
+    for player in [&ts.player1, &ts.player2] {
+        assert_error(
+            &ts.client
+                .try_submit_zk_proof(&890u32, player, &proof, &pi, &10u32),
+            Error::GameAlreadyResolved,
+        );
+    }
+}
+
+#[test]
+fn test_late_submission_ignored() {
+    let ts = setup();
+    ts.client.set_late_submit_policy(&LateSubmitPolicy::Ignore);
+    let pi = start_first_won(&ts, 891);
+    let before = ts.client.get_game(&891u32);
+    let proof = valid_proof(&ts.env);
+    for player in [&ts.player1, &ts.player2] {
+        assert_eq!(
+            ts.client
+                .submit_zk_proof(&891u32, player, &proof, &pi, &5u32),
+            None
+        );
+    }
+    assert_eq!(ts.client.get_game(&891u32), before);
+    assert_eq!(ts.client.get_outcome(&891u32), Outcome::Player1Won);
+
+    // Outsiders are still refused.
+    let outsider = Address::generate(&ts.env);
+    assert_error(
+        &ts.client
+            .try_submit_zk_proof(&891u32, &outsider, &proof, &pi, &5u32),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_late_submission_recorded() {
+    let ts = setup();
+    ts.client.set_late_submit_policy(&LateSubmitPolicy::Record);
+    let pi = start_first_won(&ts, 892);
+    let proof = valid_proof(&ts.env);
+    ts.client
+        .submit_zk_proof(&892u32, &ts.player1, &proof, &pi, &10u32);
+    ts.client
+        .submit_zk_proof(&892u32, &ts.player2, &proof, &pi, &5u32);
+    ts.client
+        .submit_zk_proof(&892u32, &ts.player2, &proof, &pi, &5u32);
+
+    let game = ts.client.get_game(&892u32);
+    assert_eq!(game.player1_late_attempts, 1);
+    assert_eq!(game.player2_late_attempts, 2);
+    assert_eq!(game.player2_energy, None);
+    assert_eq!(ts.client.get_outcome(&892u32), Outcome::Player1Won);
+}
+
+// ============================================================================
+// Active Session Index
+// ============================================================================
+
+/// Simulate `session_id`'s game expiring from temporary storage.
+fn expire_game(ts: &TestSetup, session_id: u32) {
+    ts.env.as_contract(&ts.client.address, || {
+        ts.env
+            .storage()
+            .temporary()
+            .remove(&crate::DataKey::Game(session_id));
+    });
+}
+
+#[test]
+fn test_active_sessions_listed_until_resolved() {
+    let ts = setup();
+    assert!(ts.client.list_active_sessions().is_empty());
+    start_resolvable(&ts, 900);
+    start(&ts, 901);
+    start_resolvable(&ts, 902);
+    assert_eq!(
+        ts.client.list_active_sessions(),
+        vec![&ts.env, 900u32, 901, 902]
+    );
+
+    ts.client.resolve_game(&900u32);
+    ts.client.resolve_game(&902u32);
+    assert_eq!(ts.client.list_active_sessions(), vec![&ts.env, 901u32]);
+}
+
+#[test]
+fn test_active_session_removal_moves_last_entry_into_its_slot() {
+    let ts = setup();
+    use_recording_hub(&ts);
+    start_resolvable(&ts, 961);
+    start_resolvable(&ts, 962);
+    start(&ts, 963);
+    ts.client.resolve_game(&961u32);
+    assert_eq!(ts.client.list_active_sessions(), vec![&ts.env, 963u32, 962]);
+
+    start(&ts, 964);
+    ts.client.resolve_game(&962u32);
+    assert_eq!(ts.client.list_active_sessions(), vec![&ts.env, 963u32, 964]);
+    assert_eq!(ts.client.active_session_count(), 2);
+    ts.client.cancel_game(&964u32, &None);
+    ts.client.cancel_game(&963u32, &None);
+    assert!(ts.client.list_active_sessions().is_empty());
+}
+
+#[test]
+fn test_prune_expired_drops_only_stale_entries() {
+    let ts = setup();
+    start(&ts, 903);
+    start(&ts, 904);
+    expire_game(&ts, 903);
+    assert_eq!(ts.client.list_active_sessions(), vec![&ts.env, 903u32, 904]);
+    assert_error(&ts.client.try_get_game(&903u32), Error::GameNotFound);
+
+    // 904 is live and 999 was never listed.
+    assert_eq!(ts.client.prune_expired(&vec![&ts.env, 903u32, 904, 999]), 1);
+    assert_eq!(ts.client.list_active_sessions(), vec![&ts.env, 904u32]);
+    assert_eq!(ts.client.prune_expired(&vec![&ts.env, 903u32]), 0);
+}
+
+// ============================================================================
+// State Proofs
+// ============================================================================
+
+/// Recompute a `StateProof` commitment the way a light client would.
+fn state_commitment(env: &Env, proof: &StateProof, outcome_code: u8) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &proof.session_id.to_le_bytes());
+    preimage.append(&proof.state_hash.clone().into());
+    preimage.push_back(proof.resolved as u8);
+    preimage.push_back(outcome_code);
+    preimage.extend_from_array(&proof.ledger.to_le_bytes());
+    env.crypto().keccak256(&preimage).into()
+}
+
+#[test]
+fn test_state_proof_matches_recomputed_hash() {
+    let ts = setup();
+    assert_error(&ts.client.try_state_proof(&910u32), Error::GameNotFound);
+    start_resolvable(&ts, 910);
+
+    let live = ts.client.state_proof(&910u32);
+    assert_eq!(live.state_hash, ts.client.game_state_hash(&910u32));
+    assert_eq!((live.resolved, live.ledger), (false, 100));
+    assert_eq!(live.commitment, state_commitment(&ts.env, &live, 3));
+
+    // Resolving changes the state, so the cached proof is now stale.
+    ts.client.resolve_game(&910u32);
+    assert_ne!(ts.client.game_state_hash(&910u32), live.state_hash);
+    let resolved = ts.client.state_proof(&910u32);
+    assert_eq!(resolved.state_hash, ts.client.game_state_hash(&910u32));
+    assert_eq!(
+        (resolved.resolved, resolved.outcome.clone()),
+        (true, Outcome::Player1Won)
+    );
+    assert_eq!(resolved.commitment, state_commitment(&ts.env, &resolved, 0));
+}
+
+// ============================================================================
+// Point Validation
+// ============================================================================
+
+#[test]
+fn test_non_positive_points_rejected() {
+    let ts = setup();
+    let hash = test_treasure_hash(&ts.env);
+    for (p1, p2) in [
+        (0, POINTS),
+        (POINTS, -1),
+        (i128::MIN, POINTS),
+        (POINTS, MAX_POINTS_CEILING + 1),
+    ] {
+        assert_error(
+            &ts.client
+                .try_start_game(&920u32, &ts.player1, &ts.player2, &p1, &p2, &hash),
+            Error::InvalidPoints,
+        );
+    }
+    assert_error(
+        &ts.client.try_join_queue(&ts.player1, &0),
+        Error::InvalidPoints,
+    );
+
+    ts.client.start_game(
+        &920u32,
+        &ts.player1,
+        &ts.player2,
+        &1,
+        &MAX_POINTS_CEILING,
+        &hash,
+    );
+    assert_eq!(ts.client.get_game(&920u32).player1_points, 1);
+}
+
+// ============================================================================
+// Automatic Session Ids
+// ============================================================================
+
+#[test]
+fn test_start_game_auto_allocates_monotonic_free_ids() {
+    let ts = setup();
+    assert_eq!(ts.client.peek_next_session_id(), 1);
+    // Ids taken by hand are skipped.
+    start(&ts, 2);
+    start(&ts, 3);
+
+    let hash = test_treasure_hash(&ts.env);
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..3 {
+        let player1 = Address::generate(&ts.env);
+        let player2 = Address::generate(&ts.env);
+        let expected = ts.client.peek_next_session_id();
+        let id = ts
+            .client
+            .start_game_auto(&player1, &player2, &POINTS, &POINTS, &hash);
+        assert_eq!(id, expected);
+        assert_eq!(ts.client.get_game(&id).player1, player1);
+        ids.push(id);
+    }
+    assert_eq!(ids, std::vec![1, 4, 5]);
+    assert_eq!(ts.client.peek_next_session_id(), 6);
+
+    // A failed start does not consume the id.
+    assert_error(
+        &ts.client
+            .try_start_game_auto(&ts.player1, &ts.player2, &0, &POINTS, &hash),
+        Error::InvalidPoints,
+    );
+    assert_eq!(ts.client.peek_next_session_id(), 6);
+}
+
+#[test]
+fn test_peek_next_session_id_scan_is_bounded() {
+    let ts = setup();
+    use_recording_hub(&ts);
+    let hash = test_treasure_hash(&ts.env);
+    for session_id in 1..=50u32 {
+        let player1 = Address::generate(&ts.env);
+        let player2 = Address::generate(&ts.env);
+        ts.client
+            .start_game(&session_id, &player1, &player2, &POINTS, &POINTS, &hash);
+    }
+    assert_error(
+        &ts.client.try_peek_next_session_id(),
+        Error::NoFreeSessionId,
+    );
+    assert_error(
+        &ts.client
+            .try_start_game_auto(&ts.player1, &ts.player2, &POINTS, &POINTS, &hash),
+        Error::NoFreeSessionId,
+    );
+
+    // Freeing an id within the window makes it allocatable again.
+    ts.client.cancel_game(&7u32, &None);
+    assert_eq!(ts.client.peek_next_session_id(), 7);
+}
+
+// ============================================================================
+// Forfeit
+// ============================================================================
+
+#[test]
+fn test_forfeit_before_opponent_submits() {
+    let ts = setup();
+    let hash = start(&ts, 930);
+    assert_eq!(ts.client.forfeit(&930u32, &ts.player1), None);
+    let game = ts.client.get_game(&930u32);
+    assert_eq!(game.forfeited_by, Some(ts.player1.clone()));
+    assert!(!game.resolved);
+    assert_eq!(ts.client.peek_outcome(&930u32), Outcome::Player2Won);
+
+    // Even a better proof by the forfeiting player no longer counts.
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&930u32, &ts.player1, &valid_proof(&ts.env), &pi, &1u32);
+    assert_error(
+        &ts.client.try_forfeit(&930u32, &ts.player2),
+        Error::AlreadyForfeited,
+    );
+    assert_eq!(ts.client.resolve_game(&930u32), Outcome::Player2Won);
+    assert_eq!(ts.client.get_winner(&930u32), Some(ts.player2.clone()));
+}
+
+#[test]
+fn test_forfeit_nobody_submitted_resolves_for_opponent() {
+    let ts = setup();
+    start(&ts, 931);
+    ts.client.forfeit(&931u32, &ts.player2);
+    assert_eq!(ts.client.resolve_game(&931u32), Outcome::Player1Won);
+}
+
+#[test]
+fn test_forfeit_after_opponent_submits_resolves_at_once() {
+    let ts = setup();
+    let hash = start(&ts, 932);
+    let pi = treasure_hash_as_bytes(&ts.env, &hash);
+    ts.client
+        .submit_zk_proof(&932u32, &ts.player2, &valid_proof(&ts.env), &pi, &10u32);
+
+    assert_eq!(
+        ts.client.forfeit(&932u32, &ts.player1),
+        Some(Outcome::Player2Won)
+    );
+    assert!(ts.client.get_game(&932u32).resolved);
+    assert_error(
+        &ts.client.try_forfeit(&932u32, &ts.player2),
+        Error::GameAlreadyResolved,
+    );
+}
+
+#[test]
+fn test_forfeit_by_non_player_rejected() {
+    let ts = setup();
+    start(&ts, 933);
+    let outsider = Address::generate(&ts.env);
+    assert_error(&ts.client.try_forfeit(&933u32, &outsider), Error::NotPlayer);
+}
@@ -26,6 +26,30 @@ pub struct GameEnded {
     pub player1_won: bool,
 }
 
+#[contractevent]
+pub struct GameEndedWithOutcome {
+    pub session_id: u32,
+    pub outcome: u32,
+}
+
+#[contractevent]
+pub struct GameSettled {
+    pub session_id: u32,
+    pub winner: Address,
+    pub winner_stake: i128,
+    pub loser_stake: i128,
+}
+
+#[contractevent]
+pub struct GameRefunded {
+    pub session_id: u32,
+}
+
+#[contractevent]
+pub struct GameCancelled {
+    pub session_id: u32,
+}
+
 #[contractimpl]
 impl MockGameHub {
     /// Start a game session
@@ -77,6 +101,62 @@ impl MockGameHub {
         }
         .publish(&env);
     }
+
+    /// End a game session with an explicit result
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `outcome` - 0 = player1 won, 1 = player2 won, 2 = draw, 3 = nobody won
+    pub fn end_game_with_outcome(env: Env, session_id: u32, outcome: u32) {
+        // No auth required for mock
+        GameEndedWithOutcome {
+            session_id,
+            outcome,
+        }
+        .publish(&env);
+    }
+
+    /// End a game session reporting both committed stakes
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `winner` - Address of the winning player
+    /// * `winner_stake` - Points committed by the winner
+    /// * `loser_stake` - Points committed by the loser
+    pub fn settle_with_stakes(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        winner_stake: i128,
+        loser_stake: i128,
+    ) {
+        // No auth required for mock
+        GameSettled {
+            session_id,
+            winner,
+            winner_stake,
+            loser_stake,
+        }
+        .publish(&env);
+    }
+
+    /// Cancel a game session, releasing both players' points
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being refunded
+    pub fn refund_game(env: Env, session_id: u32) {
+        // No auth required for mock
+        GameRefunded { session_id }.publish(&env);
+    }
+
+    /// Forget a game session neither player verified, unlocking their points
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being cancelled
+    pub fn cancel_game(env: Env, session_id: u32) {
+        // No auth required for mock
+        GameCancelled { session_id }.publish(&env);
+    }
 }
 
 #[cfg(test)]
@@ -86,12 +166,16 @@ mod test {
     #[test]
     fn test_start_and_end_game() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, MockGameHub);
+        let contract_id = env.register(MockGameHub, ());
         let client = MockGameHubClient::new(&env, &contract_id);
         let game_id = Address::generate(&env);
         let player1 = Address::generate(&env);
         let player2 = Address::generate(&env);
         client.start_game(&game_id, &1, &player1, &player2, &1000, &1000);
         client.end_game(&1, &true);
+        client.end_game_with_outcome(&1, &2);
+        client.settle_with_stakes(&2, &player1, &1000, &500);
+        client.refund_game(&3);
+        client.cancel_game(&4);
     }
 }